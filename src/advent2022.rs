@@ -1,4 +1,4 @@
-use crate::{Puzzle, Solution};
+use crate::{Animation, Diagram, Drawing, Puzzle, Solution};
 
 mod day01;
 mod day02;
@@ -61,6 +61,30 @@ pub fn solution(day: usize, puzzle: Puzzle) -> Solution {
     }
 }
 
+pub fn drawing(day: usize) -> Option<Drawing> {
+    match day {
+        14 => Some(day14::drawing),
+        _ => None,
+    }
+}
+
+pub fn diagram(day: usize) -> Option<Diagram> {
+    match day {
+        16 => Some(day16::diagram),
+        _ => None,
+    }
+}
+
+pub fn animation(day: usize) -> Option<Animation> {
+    match day {
+        14 => Some(day14::animation),
+        17 => Some(day17::animation),
+        23 => Some(day23::animation),
+        24 => Some(day24::animation),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::fmt::Display;
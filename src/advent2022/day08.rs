@@ -37,7 +37,7 @@ fn visibility_from_edge(
     from_corner: Position,
     to_corner: Position,
 ) {
-    let edge_direction = to_corner.sub(from_corner).unit();
+    let edge_direction = to_corner.sub(from_corner).signum();
     let mut position = from_corner;
     while position != to_corner {
         visibility_along_line(grid, visibility, position, edge_direction.right());
@@ -37,16 +37,13 @@ fn step(rope: &mut [Position], direction: Direction) {
     }
 }
 
-fn knot_position(knot @ [mut row, mut column]: Position, puller: Position) -> Position {
-    let [row_difference, column_difference] = puller.sub(knot);
-    if row_difference.abs() > 1 {
-        row += row_difference / 2;
-        column += column_difference.signum();
-    } else if column_difference.abs() > 1 {
-        column += column_difference / 2;
-        row += row_difference.signum();
+fn knot_position(knot: Position, puller: Position) -> Position {
+    let difference = puller.sub(knot);
+    if difference.chebyshev() > 1 {
+        knot.add(difference.signum())
+    } else {
+        knot
     }
-    [row, column]
 }
 
 fn motions(input: &str) -> impl Iterator<Item = Motion> + '_ {
@@ -1,6 +1,6 @@
 use itertools::Itertools;
 
-use crate::string::usizes;
+use crate::{number_theory, string::usizes};
 
 struct Monkey {
     items: Vec<Worry>,
@@ -21,7 +21,7 @@ pub fn second(input: &str) -> String {
 }
 
 fn monkey_business(monkeys: &mut [Monkey], number_of_rounds: usize, worry_divisor: Worry) -> Worry {
-    let worry_modulo = monkeys.iter().map(|monkey| monkey.divisor).product();
+    let worry_modulo = number_theory::lcm_all(monkeys.iter().map(|monkey| monkey.divisor));
     let mut number_of_inspections = vec![0; monkeys.len()];
     for _ in 0..number_of_rounds {
         round(
@@ -3,6 +3,8 @@ use itertools::Itertools;
 
 use crate::{
     grid::{self, Coordinate, Grid, Position},
+    image::{self, Image},
+    simulation::Simulation,
     string::isizes,
     vector::Vector,
 };
@@ -25,6 +27,41 @@ pub fn second(input: &str) -> String {
     number_of_units_of_sand(&cave).to_string()
 }
 
+pub fn drawing(input: &str) -> Image {
+    let (mut cave, sand_source) = cave(input);
+    let stop = |rest_position: Position| rest_position == sand_source;
+    produce_sand(&mut cave, sand_source, stop);
+    let mut image = Image::new(&cave, |_, &element| match element {
+        b'#' => image::GRAY,
+        b'o' => image::YELLOW,
+        _ => image::BLACK,
+    });
+    image.highlight([sand_source], image::RED);
+    image
+}
+
+pub fn animation(input: &str) -> Box<dyn Simulation> {
+    let (cave, sand_source) = cave(input);
+    Box::new(FallingSand { cave, sand_source })
+}
+
+struct FallingSand {
+    cave: Cave,
+    sand_source: Position,
+}
+
+impl Simulation for FallingSand {
+    fn frame(&self) -> Grid<char> {
+        self.cave.map(|_, &element| char::from(element))
+    }
+
+    fn step(&mut self) -> bool {
+        let rest_position = rest_position(&self.cave, self.sand_source);
+        self.cave[rest_position] = b'o';
+        rest_position != self.sand_source
+    }
+}
+
 fn number_of_units_of_sand(cave: &Cave) -> usize {
     cave.iter_row_major()
         .filter(|(_, &element)| element == b'o')
@@ -97,7 +134,7 @@ fn add_path(cave: &mut Cave, path: &[Position], [_, sand_source_column]: Positio
         *element = b'#';
     }
     for joint in path {
-        let direction = joint.sub(position).unit();
+        let direction = joint.sub(position).signum();
         while position != joint {
             position = position.add(direction);
             if let Some(element) = cave.get_mut(position) {
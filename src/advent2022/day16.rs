@@ -1,16 +1,22 @@
 use std::{cmp, sync::OnceLock};
 
+use easy_cast::Cast;
 use itertools::Itertools;
 use regex::Regex;
 
-use crate::{search::distances, string::usizes, HashMap, HashSet};
+use crate::{
+    dot::Dot,
+    graph::contract,
+    search::branch_and_bound::{self, Options, Traversal},
+    string::usizes,
+    HashMap,
+};
 
 type ContractedCave<'input> = HashMap<Valve<'input>, (Pressure, Vec<(Valve<'input>, Time)>)>;
 type Cave<'input> = HashMap<Valve<'input>, (Pressure, Vec<Valve<'input>>)>;
 type Valve<'input> = &'input str;
 type Pressure = usize;
 type Time = usize;
-type Distance = usize;
 
 pub fn first(input: &str) -> String {
     maximum_release_from_input::<1>(input, "AA", 30).to_string()
@@ -20,6 +26,28 @@ pub fn second(input: &str) -> String {
     maximum_release_from_input::<2>(input, "AA", 26).to_string()
 }
 
+pub fn diagram(input: &str) -> Dot {
+    let start = "AA";
+    let contracted_cave = contracted_cave(&cave(input), start);
+    let tunnels = contracted_cave
+        .iter()
+        .sorted_unstable_by_key(|&(&valve, _)| valve)
+        .map(|(&valve, (_, tunnels))| (valve, tunnels.iter().sorted()));
+    Dot::from_adjacency(
+        false,
+        tunnels,
+        |valve| {
+            let flow = contracted_cave[valve].0;
+            let mut attributes = vec![("label", format!("{valve} ({flow})"))];
+            if *valve == start {
+                attributes.push(("shape", "doublecircle".to_owned()));
+            }
+            attributes
+        },
+        |_, &(valve, distance)| (valve, vec![("label", distance.to_string())]),
+    )
+}
+
 fn maximum_release_from_input<const NUM_AGENTS: usize>(
     input: &str,
     start: Valve,
@@ -27,74 +55,113 @@ fn maximum_release_from_input<const NUM_AGENTS: usize>(
 ) -> Pressure {
     let cave = cave(input);
     let contracted_cave = contracted_cave(&cave, start);
-    let mut closed_valves = contracted_cave
-        .iter()
-        .filter_map(|(&valve, &(flow, _))| (flow != 0).then_some(valve))
-        .collect();
-    maximum_release(
-        &contracted_cave,
-        &mut closed_valves,
-        [(start, time); NUM_AGENTS],
-    )
+    maximum_release(&contracted_cave, [(start, time); NUM_AGENTS])
 }
 
+type State<'input, const NUM_AGENTS: usize> =
+    ([(Valve<'input>, Time); NUM_AGENTS], ClosedValves, Pressure);
+type ClosedValves = u64;
+
 fn maximum_release<'input, const NUM_AGENTS: usize>(
     cave: &ContractedCave<'input>,
-    closed_valves: &mut HashSet<Valve<'input>>,
-    mut agents: [(Valve<'input>, Time); NUM_AGENTS],
+    agents: [(Valve<'input>, Time); NUM_AGENTS],
 ) -> Pressure {
-    agents.sort_unstable_by_key(|&(_, time)| Time::MAX - time);
-    let (current_valve, current_time) = agents[0];
-    if current_time == 0 {
-        return 0;
-    }
+    let functioning_valves = cave
+        .iter()
+        .filter_map(|(&valve, &(flow, _))| (flow != 0).then_some(valve))
+        .sorted()
+        .collect_vec();
+    assert!(
+        functioning_valves.len() <= ClosedValves::BITS.cast(),
+        "there should be at most 64 functioning valves"
+    );
+    let flows = functioning_valves
+        .iter()
+        .map(|valve| cave[valve].0)
+        .collect_vec();
+    let distances: HashMap<Valve, Vec<Option<Time>>> = cave
+        .iter()
+        .map(|(&from, (_, tunnels))| {
+            let distances = functioning_valves
+                .iter()
+                .map(|&to| {
+                    tunnels
+                        .iter()
+                        .find_map(|&(valve, distance)| (valve == to).then_some(distance))
+                })
+                .collect_vec();
+            (from, distances)
+        })
+        .collect();
 
-    let mut maximum_release = 0;
-    for &(valve, distance) in &cave[current_valve].1 {
-        if !closed_valves.contains(valve) {
-            continue;
+    let successors = |&(agents, closed_valves, release): &State<'input, NUM_AGENTS>| {
+        let (current_valve, current_time) = agents[0];
+        if current_time == 0 {
+            return vec![];
         }
-
-        let Some(time) = current_time.checked_sub(distance + 1) else {
-            continue;
-        };
-        let (flow, _) = cave[valve];
-        let valve_release = time * flow;
-
-        agents[0] = (valve, time);
-        closed_valves.remove(valve);
-        let maximum_remaining_release = self::maximum_release(cave, closed_valves, agents);
-        closed_valves.insert(valve);
-
-        maximum_release = cmp::max(maximum_release, valve_release + maximum_remaining_release);
-    }
-
-    agents[0] = (current_valve, 0);
-    let stop_release = self::maximum_release(cave, closed_valves, agents);
-    maximum_release = cmp::max(maximum_release, stop_release);
-
+        let mut successors = closed(closed_valves)
+            .filter_map(|index| {
+                let distance = distances[current_valve][index]?;
+                let time = current_time.checked_sub(distance + 1)?;
+                let agents = sorted(agents, (functioning_valves[index], time));
+                let closed_valves = closed_valves & !(1 << index);
+                Some((agents, closed_valves, release + time * flows[index]))
+            })
+            .collect_vec();
+        successors.push((sorted(agents, (current_valve, 0)), closed_valves, release));
+        successors
+    };
+    let optimistic_release = |&(agents, closed_valves, release): &State<'input, NUM_AGENTS>| {
+        let optimistic_remaining_release: Pressure = closed(closed_valves)
+            .map(|index| {
+                let most_remaining_time = agents
+                    .iter()
+                    .filter_map(|&(valve, time)| time.checked_sub(distances[valve][index]? + 1))
+                    .max()
+                    .unwrap_or_default();
+                most_remaining_time * flows[index]
+            })
+            .sum();
+        release + optimistic_remaining_release
+    };
+    // Of the four combinations, this one measured fastest for part two, which takes the longest.
+    let options = Options {
+        traversal: Traversal::BestFirst,
+        memoise: true,
+    };
+    let all_closed = (1 << functioning_valves.len()) - 1;
+    let (maximum_release, _) = branch_and_bound::maximize(
+        (sorted(agents, agents[0]), all_closed, 0),
+        successors,
+        |&(_, _, release)| release,
+        optimistic_release,
+        options,
+    );
     maximum_release
 }
 
-fn contracted_cave<'input>(cave: &Cave<'input>, start: Valve<'input>) -> ContractedCave<'input> {
-    cave.iter()
-        .filter(|(&valve, &(flow, _))| flow != 0 || valve == start)
-        .map(|(&source, &(flow, _))| {
-            let distances = distances_to_functioning_valves(cave, source);
-            (source, (flow, distances))
-        })
-        .collect()
+/// Replaces the agent with the most remaining time, and sorts the agents by decreasing remaining
+/// time so that the agent with the most remaining time moves next.
+fn sorted<'input, const NUM_AGENTS: usize>(
+    mut agents: [(Valve<'input>, Time); NUM_AGENTS],
+    moved: (Valve<'input>, Time),
+) -> [(Valve<'input>, Time); NUM_AGENTS] {
+    agents[0] = moved;
+    agents.sort_unstable_by_key(|&(valve, time)| (cmp::Reverse(time), valve));
+    agents
+}
+
+fn closed(closed_valves: ClosedValves) -> impl Iterator<Item = usize> {
+    (0..ClosedValves::BITS.cast()).filter(move |&index| closed_valves & (1 << index) != 0)
 }
 
-fn distances_to_functioning_valves<'input>(
-    cave: &Cave<'input>,
-    source: Valve<'input>,
-) -> Vec<(Valve<'input>, Distance)> {
-    let successors = |name| cave[name].1.iter().copied();
-    distances(source, successors)
+fn contracted_cave<'input>(cave: &Cave<'input>, start: Valve<'input>) -> ContractedCave<'input> {
+    let successors = |valve| cave[valve].1.iter().copied();
+    let is_functioning = |valve| cave[valve].0 != 0;
+    contract::distances([start], successors, is_functioning)
         .into_iter()
-        .filter(|&(valve, _)| cave[valve].0 != 0 && valve != source)
-        .collect_vec()
+        .map(|(valve, tunnels)| (valve, (cave[valve].0, tunnels)))
+        .collect()
 }
 
 fn cave(input: &str) -> Cave {
@@ -131,11 +198,10 @@ mod tests {
         test_on_input(DAY, Puzzle::Second, Input::Example(0), 1707);
     }
 
-    // #[test]
-    // fn second_input() {
-    //     // This takes several minutes with the 'dev' compilation profile
-    //     test_on_input(DAY, Puzzle::Second, Input::PuzzleInput, 2052);
-    // }
+    #[test]
+    fn second_input() {
+        test_on_input(DAY, Puzzle::Second, Input::PuzzleInput, 2052);
+    }
 
     #[test]
     fn farther_agent_should_not_close_last_valve() {
@@ -2,6 +2,8 @@ use std::{cmp, iter::Peekable};
 
 use easy_cast::{Cast, Conv};
 
+use crate::{grid::Grid, search::cycle, simulation::Simulation};
+
 type Chamber = Vec<[bool; CHAMBER_WIDTH_INCLUDING_WALLS]>;
 type Position = [Coordinate; 2];
 type Coordinate = usize;
@@ -28,6 +30,7 @@ const CHAMBER_WIDTH: Coordinate = 7;
 const CHAMBER_WIDTH_INCLUDING_WALLS: Coordinate = 1 + CHAMBER_WIDTH + 1;
 const APPEARING_ROCK_LEFT_MARGIN: Coordinate = 2;
 const APPEARING_ROCK_BOTTOM_MARGIN: Coordinate = 3;
+const FRAME_HEIGHT: Coordinate = 40;
 
 type Surface = Vec<Direction>;
 type Direction = [isize; 2];
@@ -40,37 +43,101 @@ pub fn second(input: &str) -> String {
     tower_height(input.trim(), 1_000_000_000_000_usize).to_string()
 }
 
-fn tower_height(jets: &str, mut number_of_rocks: usize) -> Coordinate {
+pub fn animation(input: &str) -> Box<dyn Simulation> {
+    Box::new(FallingRocks {
+        jets: input.trim().chars().collect(),
+        number_of_jets: 0,
+        number_of_rocks: 0,
+        chamber: empty_chamber(),
+        tower_height: 0,
+        falling_rock: None,
+    })
+}
+
+struct FallingRocks {
+    jets: Vec<Jet>,
+    number_of_jets: usize,
+    number_of_rocks: usize,
+    chamber: Chamber,
+    tower_height: Coordinate,
+    falling_rock: Option<(Rock, Position)>,
+}
+
+impl FallingRocks {
+    fn is_falling_rock(&self, [row, column]: Position) -> bool {
+        self.falling_rock.is_some_and(|(rock, [top, left])| {
+            let [row_index, column_index] = [top.wrapping_sub(row), column.wrapping_sub(left)];
+            rock.get(row_index)
+                .and_then(|rock_row| rock_row.get(column_index))
+                .is_some_and(|&is_rock| is_rock)
+        })
+    }
+}
+
+impl Simulation for FallingRocks {
+    fn frame(&self) -> Grid<char> {
+        let top = self.chamber.len() - 1;
+        let height = cmp::min(FRAME_HEIGHT, self.chamber.len());
+        Grid::new(height, CHAMBER_WIDTH_INCLUDING_WALLS, |[row, column]| {
+            let position @ [row, column] = [top - usize::conv(row), column.cast()];
+            let is_wall = column == 0 || column == CHAMBER_WIDTH_INCLUDING_WALLS - 1;
+            match (row, is_wall) {
+                (0, true) => '+',
+                (0, false) => '-',
+                (_, true) => '|',
+                _ if self.is_falling_rock(position) => '@',
+                _ if self.chamber[row][column] => '#',
+                _ => '.',
+            }
+        })
+    }
+
+    fn step(&mut self) -> bool {
+        match self.falling_rock {
+            None => {
+                let rock = ROCKS[self.number_of_rocks % ROCKS.len()];
+                let position @ [top, _] = initial_position(self.tower_height, rock);
+                ensure_needed_height(&mut self.chamber, top);
+                self.falling_rock = Some((rock, position));
+            }
+            Some((rock, position)) => {
+                let jet = self.jets[self.number_of_jets % self.jets.len()];
+                self.number_of_jets += 1;
+                let [top, left] = pushed(&self.chamber, rock, position, jet);
+                if collision(&self.chamber, rock, [top - 1, left]) {
+                    stop_rock(&mut self.chamber, rock, [top, left]);
+                    self.tower_height = cmp::max(self.tower_height, top);
+                    self.number_of_rocks += 1;
+                    self.falling_rock = None;
+                } else {
+                    self.falling_rock = Some((rock, [top - 1, left]));
+                }
+            }
+        }
+        true
+    }
+}
+
+fn tower_height(jets: &str, number_of_rocks: usize) -> Coordinate {
     let mut jets = jets.chars().enumerate().cycle().peekable();
     let mut chamber = empty_chamber();
-    let mut tower_height = 0;
-    let mut past_states_and_tower_heights =
-        vec![(state(&mut jets, &chamber, tower_height), tower_height)];
-    for rock in ROCKS.into_iter().cycle() {
-        if number_of_rocks == 0 {
+    let mut tower_heights = vec![0];
+    let mut detector = cycle::Detector::default();
+    for (rock_index, rock) in ROCKS.into_iter().enumerate().cycle() {
+        let tower_height = *tower_heights
+            .last()
+            .expect("there should be a tower height");
+        if tower_heights.len() > number_of_rocks {
             return tower_height;
         }
 
-        let rock_top = drop_rock(&mut jets, &mut chamber, tower_height, rock);
-        tower_height = cmp::max(tower_height, rock_top);
-        number_of_rocks -= 1;
-
-        let state = state(&mut jets, &chamber, tower_height);
-        if let Some((cycle_length, tower_height_cycle_start)) =
-            cycle_length_and_tower_height_cycle_start(&past_states_and_tower_heights, &state)
-        {
-            let remaining_cycles = number_of_rocks / cycle_length;
-            let tower_height_complete_cycles =
-                (tower_height - tower_height_cycle_start) * remaining_cycles;
-            number_of_rocks %= cycle_length;
-            let incomplete_cycle_end_index =
-                past_states_and_tower_heights.len() - cycle_length + number_of_rocks;
-            let tower_height_incomplete_cycle =
-                past_states_and_tower_heights[incomplete_cycle_end_index].1
-                    - tower_height_cycle_start;
-            return tower_height + tower_height_complete_cycles + tower_height_incomplete_cycle;
+        let state = (rock_index, state(&mut jets, &chamber, tower_height));
+        if let Some(cycle) = detector.observe(state) {
+            return cycle::extrapolate(&tower_heights, cycle, number_of_rocks);
         }
-        past_states_and_tower_heights.push((state, tower_height));
+
+        let rock_top = drop_rock(&mut jets, &mut chamber, tower_height, rock);
+        tower_heights.push(cmp::max(tower_height, rock_top));
     }
     unreachable!("rocks should cycle indefinitely");
 }
@@ -98,27 +165,32 @@ fn landing_position(
     jets: &mut impl Iterator<Item = (usize, Jet)>,
     chamber: &Chamber,
     rock: Rock,
-    [mut top, mut left]: Position,
+    mut position: Position,
 ) -> Position {
     for (_, jet) in jets {
-        let next_left = match jet {
-            '<' => left - 1,
-            '>' => left + 1,
-            _ => panic!("jet should be '<' or '>'"),
-        };
-        if !collision(chamber, rock, [top, next_left]) {
-            left = next_left;
-        }
-
+        let [top, left] = pushed(chamber, rock, position, jet);
         let next_top = top - 1;
         if collision(chamber, rock, [next_top, left]) {
             return [top, left];
         }
-        top = next_top;
+        position = [next_top, left];
     }
     panic!("jets should repeat indefinitely");
 }
 
+fn pushed(chamber: &Chamber, rock: Rock, [top, left]: Position, jet: Jet) -> Position {
+    let next_left = match jet {
+        '<' => left - 1,
+        '>' => left + 1,
+        _ => panic!("jet should be '<' or '>'"),
+    };
+    if collision(chamber, rock, [top, next_left]) {
+        [top, left]
+    } else {
+        [top, next_left]
+    }
+}
+
 fn collision(chamber: &Chamber, rock: Rock, [row, column]: Position) -> bool {
     for (row_index, &rock_row) in rock.iter().enumerate() {
         for (column_index, &is_rock) in rock_row.iter().enumerate() {
@@ -154,25 +226,6 @@ fn state(
     (jet_index, surface)
 }
 
-fn cycle_length_and_tower_height_cycle_start(
-    past_states_and_tower_heights: &[(State, Coordinate)],
-    (jet_index, surface): &State,
-) -> Option<(usize, Coordinate)> {
-    let mut index = past_states_and_tower_heights.len();
-    while ROCKS.len() <= index {
-        index -= ROCKS.len();
-        let ((past_jet_index, past_surface), past_tower_height) =
-            &past_states_and_tower_heights[index];
-        if jet_index == past_jet_index && surface == past_surface {
-            return Some((
-                past_states_and_tower_heights.len() - index,
-                *past_tower_height,
-            ));
-        }
-    }
-    None
-}
-
 fn surface(chamber: &Chamber, tower_height: Coordinate) -> Surface {
     let left = |[row, column]: Direction| [column, -row];
     let right = |[row, column]: Direction| [-column, row];
@@ -268,50 +321,6 @@ mod tests {
         );
     }
 
-    #[test]
-    fn some_cycle() {
-        let flat = vec![RIGHT, RIGHT, RIGHT, RIGHT, RIGHT, RIGHT];
-        let bumpy = vec![RIGHT, RIGHT, UP, RIGHT, RIGHT, DOWN, RIGHT, RIGHT];
-        let past_states = [
-            ((0, flat.clone()), 0),
-            ((1, flat.clone()), 1),
-            ((0, bumpy.clone()), 2),
-            ((1, bumpy.clone()), 3),
-            ((0, flat.clone()), 4),
-            ((1, flat.clone()), 5),
-            ((0, flat.clone()), 6),
-            ((1, flat.clone()), 7),
-            ((0, flat.clone()), 8),
-            ((1, bumpy.clone()), 9),
-        ];
-        let state = (0, flat);
-        let actual = cycle_length_and_tower_height_cycle_start(&past_states, &state);
-        let expected = Some((10, 0));
-        assert_eq!(actual, expected);
-    }
-
-    #[test]
-    fn no_cycle() {
-        let flat = vec![RIGHT, RIGHT, RIGHT, RIGHT, RIGHT, RIGHT];
-        let bumpy = vec![RIGHT, RIGHT, UP, RIGHT, RIGHT, DOWN, RIGHT, RIGHT];
-        let past_states = [
-            ((0, flat.clone()), 0),
-            ((1, flat.clone()), 1),
-            ((2, bumpy.clone()), 2),
-            ((0, bumpy.clone()), 3),
-            ((1, flat.clone()), 4),
-            ((2, bumpy.clone()), 5),
-            ((0, bumpy.clone()), 6),
-            ((1, flat.clone()), 7),
-            ((2, flat.clone()), 8),
-            ((0, bumpy.clone()), 9),
-        ];
-        let state = (0, bumpy);
-        let actual = cycle_length_and_tower_height_cycle_start(&past_states, &state);
-        let expected = None;
-        assert_eq!(actual, expected);
-    }
-
     #[test]
     fn surface_ten_rocks_in_chamber() {
         let expected = vec![
@@ -2,6 +2,8 @@ use std::cmp;
 
 use itertools::Itertools;
 
+use crate::search::branch_and_bound::{self, Options, Traversal};
+
 const NUMBER_OF_RESOURCES: usize = 4;
 
 const ORE: usize = 0;
@@ -41,38 +43,39 @@ type State = (Time, RobotCounts, Resources);
 
 fn maximum_geodes(time_limit: Time, blueprint: Blueprint) -> Count {
     let maximum_needed_income = maximum_needed_income(blueprint);
-
-    let mut maximum_geodes = 0;
-    let mut states: Vec<State> = vec![(0, [1, 0, 0, 0], [0, 0, 0, 0])];
-    while let Some((time, robot_counts, resources)) = states.pop() {
-        let geodes_no_additional_robots =
-            resource_after_time(GEODE, time_limit - time, robot_counts, resources);
-        maximum_geodes = cmp::max(maximum_geodes, geodes_no_additional_robots);
-
-        for (resource_type, robot_cost) in blueprint.into_iter().enumerate().rev() {
-            if robot_counts[resource_type] == maximum_needed_income[resource_type] {
-                continue;
-            }
-
-            let Some((additional_time, robot_counts, resources)) =
-                robot_building(resource_type, robot_cost, robot_counts, resources)
-            else {
-                continue;
-            };
-            let time = time + additional_time;
-
-            if time >= time_limit {
-                continue;
-            }
-            let remaining_time = time_limit - time;
-            let geodes_upper_bound = geodes_upper_bound(remaining_time, robot_counts, resources);
-            if geodes_upper_bound <= maximum_geodes {
-                continue;
-            }
-
-            states.push((time, robot_counts, resources));
-        }
-    }
+    let successors = |&(time, robot_counts, resources): &State| {
+        blueprint
+            .into_iter()
+            .enumerate()
+            .rev()
+            .filter(move |&(resource_type, _)| {
+                robot_counts[resource_type] != maximum_needed_income[resource_type]
+            })
+            .filter_map(move |(resource_type, robot_cost)| {
+                let (additional_time, robot_counts, resources) =
+                    robot_building(resource_type, robot_cost, robot_counts, resources)?;
+                let time = time + additional_time;
+                (time < time_limit).then_some((time, robot_counts, resources))
+            })
+    };
+    let geodes_no_additional_robots = |&(time, robot_counts, resources): &State| {
+        resource_after_time(GEODE, time_limit - time, robot_counts, resources)
+    };
+    let geodes_upper_bound = |&(time, robot_counts, resources): &State| {
+        geodes_upper_bound(time_limit - time, robot_counts, resources)
+    };
+    // Of the four combinations, this one measured fastest for both parts.
+    let options = Options {
+        traversal: Traversal::DepthFirst,
+        memoise: false,
+    };
+    let (maximum_geodes, _) = branch_and_bound::maximize(
+        (0, [1, 0, 0, 0], [0, 0, 0, 0]),
+        successors,
+        geodes_no_additional_robots,
+        geodes_upper_bound,
+        options,
+    );
     maximum_geodes
 }
 
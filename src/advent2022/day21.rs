@@ -1,6 +1,6 @@
 use std::ops::{Add, Div, Mul, Sub};
 
-use crate::HashMap;
+use crate::{graph::Graph, HashMap};
 
 type Monkey<'input> = (&'input str, Expression<'input>);
 
@@ -34,10 +34,24 @@ type Number = f64;
 
 pub fn first(input: &str) -> String {
     let monkeys = monkeys(input);
-    let Reduction::Number(number) = reduce(&monkeys, "root") else {
-        panic!("expression should contain no unknowns");
-    };
-    number.to_string()
+    let order = dependencies(&monkeys)
+        .topological_order()
+        .unwrap_or_else(|cycle| panic!("monkeys should not wait on themselves: {cycle:?}"));
+    let mut numbers: HashMap<&str, Number> = HashMap::new();
+    for name in order {
+        let number = match monkeys[name] {
+            Expression::Add(left, right) => numbers[left] + numbers[right],
+            Expression::Sub(left, right) => numbers[left] - numbers[right],
+            Expression::Mul(left, right) => numbers[left] * numbers[right],
+            Expression::Div(left, right) => numbers[left] / numbers[right],
+            Expression::Constant(constant) => constant,
+            Expression::Equal(..) | Expression::Unknown => {
+                panic!("expression should contain no unknowns")
+            }
+        };
+        numbers.insert(name, number);
+    }
+    numbers["root"].to_string()
 }
 
 pub fn second(input: &str) -> String {
@@ -145,6 +159,23 @@ fn reduce_binary_operator<'input>(
     }
 }
 
+fn dependencies<'input>(monkeys: &HashMap<&'input str, Expression<'input>>) -> Graph<'input> {
+    let mut dependencies = Graph::new();
+    for (&name, &expression) in monkeys {
+        dependencies.add_node(name);
+        if let Expression::Add(left, right)
+        | Expression::Sub(left, right)
+        | Expression::Mul(left, right)
+        | Expression::Div(left, right)
+        | Expression::Equal(left, right) = expression
+        {
+            dependencies.add_edge(left, name);
+            dependencies.add_edge(right, name);
+        }
+    }
+    dependencies
+}
+
 fn monkeys(input: &str) -> HashMap<&str, Expression> {
     input.lines().map(monkey).collect()
 }
@@ -4,7 +4,9 @@ use easy_cast::Cast;
 use itertools::Itertools;
 
 use crate::{
-    grid::{self, Direction, Position},
+    grid::{self, Direction, Grid, Position},
+    image::{self, Color},
+    simulation::Simulation,
     vector::{RotationInTwoDimensions, Vector},
     HashMap, HashSet,
 };
@@ -24,6 +26,51 @@ pub fn second(input: &str) -> String {
     number_of_rounds_before_steady_state.to_string()
 }
 
+pub fn animation(input: &str) -> Box<dyn Simulation> {
+    Box::new(Rounds {
+        elves: elves(input),
+        directions: INITIAL_DIRECTIONS,
+    })
+}
+
+struct Rounds {
+    elves: HashSet<Position>,
+    directions: [Direction; 4],
+}
+
+impl Simulation for Rounds {
+    fn frame(&self) -> Grid<char> {
+        let [top, left] = [0, 1].map(|coordinate_index| {
+            self.elves
+                .iter()
+                .map(|&elf| elf[coordinate_index])
+                .min()
+                .expect("at least one elf should be present")
+        });
+        let [height, width] = bounding_rectangle(&self.elves);
+        Grid::new(height, width, |position| {
+            if self.elves.contains(&position.add([top, left])) {
+                '#'
+            } else {
+                '.'
+            }
+        })
+    }
+
+    fn step(&mut self) -> bool {
+        let is_steady_state = round(&mut self.elves, self.directions);
+        self.directions.rotate_left(1);
+        !is_steady_state
+    }
+
+    fn color(&self, element: char) -> Color {
+        match element {
+            '#' => image::YELLOW,
+            _ => image::BLACK,
+        }
+    }
+}
+
 fn simulation(
     elves: &mut HashSet<Position>,
     maximum_number_of_rounds: Option<usize>,
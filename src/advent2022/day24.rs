@@ -2,7 +2,9 @@ use easy_cast::{Cast, Conv};
 use itertools::Itertools;
 
 use crate::{
-    grid::{self, Coordinate, Direction, Position},
+    grid::{self, Coordinate, Direction, Grid, Position},
+    image::{self, Color},
+    simulation::Simulation,
     vector::Vector,
     HashSet,
 };
@@ -11,6 +13,13 @@ type Blizzards = Vec<Blizzard>;
 type Blizzard = (Position, Direction);
 type Minutes = usize;
 
+const BLIZZARDS: [(char, Direction); 4] = [
+    ('^', grid::NORTH),
+    ('>', grid::EAST),
+    ('v', grid::SOUTH),
+    ('<', grid::WEST),
+];
+
 pub fn first(input: &str) -> String {
     let (dimensions @ [height, width], mut blizzards) = valley(input);
     let [start, end] = [[0, 1], [height - 1, width - 2]];
@@ -24,6 +33,76 @@ pub fn second(input: &str) -> String {
     fastest_journey(dimensions, &mut blizzards, &journey).to_string()
 }
 
+pub fn animation(input: &str) -> Box<dyn Simulation> {
+    let (valley_dimensions @ [height, width], blizzards) = valley(input);
+    let [start, end] = [[0, 1], [height - 1, width - 2]];
+    Box::new(Expedition {
+        valley_dimensions,
+        blizzards,
+        positions: HashSet::from([start]),
+        start,
+        end,
+    })
+}
+
+struct Expedition {
+    valley_dimensions: [Coordinate; 2],
+    blizzards: Blizzards,
+    positions: HashSet<Position>,
+    start: Position,
+    end: Position,
+}
+
+impl Simulation for Expedition {
+    fn frame(&self) -> Grid<char> {
+        let [height, width] = self.valley_dimensions.map(Cast::cast);
+        let mut frame = Grid::new(height, width, |position| {
+            let is_wall = is_outside_valley(self.valley_dimensions, position)
+                && ![self.start, self.end].contains(&position);
+            if is_wall {
+                '#'
+            } else {
+                '.'
+            }
+        });
+        for &(position, direction) in &self.blizzards {
+            let element = &mut frame[position];
+            *element = match element.to_digit(10) {
+                Some(number_of_blizzards) => char::from_digit(number_of_blizzards + 1, 10)
+                    .expect("at most four blizzards should share a position"),
+                None if *element == '.' => blizzard(direction),
+                None => '2',
+            };
+        }
+        for &position in &self.positions {
+            frame[position] = 'E';
+        }
+        frame
+    }
+
+    fn step(&mut self) -> bool {
+        let has_reached_end = minute(
+            self.valley_dimensions,
+            &mut self.blizzards,
+            &mut self.positions,
+            self.end,
+        );
+        if has_reached_end {
+            self.positions = HashSet::from([self.end]);
+        }
+        !has_reached_end
+    }
+
+    fn color(&self, element: char) -> Color {
+        match element {
+            '#' => image::GRAY,
+            '.' => image::BLACK,
+            'E' => image::YELLOW,
+            _ => image::BLUE,
+        }
+    }
+}
+
 fn fastest_journey(
     valley_dimensions: [Coordinate; 2],
     blizzards: &mut Blizzards,
@@ -45,29 +124,41 @@ fn fastest_path(
     let mut time = 0;
     loop {
         time += 1;
-        move_blizzards(valley_dimensions, blizzards);
-        let mut valid_neighbors = vec![];
-        for &position in &positions {
-            for neighbor in grid::neighbors(position) {
-                if neighbor == end {
-                    return time;
-                }
-                let is_on_boundary = is_outside_valley(valley_dimensions, neighbor);
-                let is_in_blizzard = blizzards
-                    .binary_search_by_key(&neighbor, |&(position, _)| position)
-                    .is_ok();
-                if !is_on_boundary && !is_in_blizzard {
-                    valid_neighbors.push(neighbor);
-                }
+        if minute(valley_dimensions, blizzards, &mut positions, end) {
+            return time;
+        }
+    }
+}
+
+fn minute(
+    valley_dimensions: [Coordinate; 2],
+    blizzards: &mut Blizzards,
+    positions: &mut HashSet<Position>,
+    end: Position,
+) -> bool {
+    move_blizzards(valley_dimensions, blizzards);
+    let mut valid_neighbors = vec![];
+    for &position in &*positions {
+        for neighbor in grid::neighbors(position) {
+            if neighbor == end {
+                return true;
+            }
+            let is_on_boundary = is_outside_valley(valley_dimensions, neighbor);
+            let is_in_blizzard = blizzards
+                .binary_search_by_key(&neighbor, |&(position, _)| position)
+                .is_ok();
+            if !is_on_boundary && !is_in_blizzard {
+                valid_neighbors.push(neighbor);
             }
         }
-        positions.retain(|position| {
-            blizzards
-                .binary_search_by_key(position, |&(position, _)| position)
-                .is_err()
-        });
-        positions.extend(valid_neighbors);
     }
+    positions.retain(|position| {
+        blizzards
+            .binary_search_by_key(position, |&(position, _)| position)
+            .is_err()
+    });
+    positions.extend(valid_neighbors);
+    false
 }
 
 fn move_blizzards(valley_dimensions: [Coordinate; 2], blizzards: &mut Blizzards) {
@@ -80,6 +171,15 @@ fn move_blizzards(valley_dimensions: [Coordinate; 2], blizzards: &mut Blizzards)
     blizzards.sort_unstable();
 }
 
+fn blizzard(direction: Direction) -> char {
+    BLIZZARDS
+        .into_iter()
+        .find_map(|(blizzard, blizzard_direction)| {
+            (blizzard_direction == direction).then_some(blizzard)
+        })
+        .expect("direction should be one of four unit vectors")
+}
+
 fn is_outside_valley([height, width]: [Coordinate; 2], [row, column]: Position) -> bool {
     row <= 0
         || row >= Coordinate::conv(height) - 1
@@ -109,13 +209,9 @@ fn valley(input: &str) -> ([Coordinate; 2], Blizzards) {
         .enumerate()
         .flat_map(|(row, line)| {
             line.chars().enumerate().filter_map(move |(column, char)| {
-                let blizzard_direction = match char {
-                    '^' => Some(grid::NORTH),
-                    '>' => Some(grid::EAST),
-                    'v' => Some(grid::SOUTH),
-                    '<' => Some(grid::WEST),
-                    _ => None,
-                }?;
+                let blizzard_direction = BLIZZARDS
+                    .into_iter()
+                    .find_map(|(blizzard, direction)| (blizzard == char).then_some(direction))?;
                 let position = [row.cast(), column.cast()];
                 Some((position, blizzard_direction))
             })
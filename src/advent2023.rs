@@ -1,4 +1,4 @@
-use crate::{Puzzle, Solution};
+use crate::{Animation, Diagram, Drawing, Puzzle, Solution};
 
 mod day01;
 mod day02;
@@ -61,6 +61,31 @@ pub fn solution(day: usize, puzzle: Puzzle) -> Solution {
     }
 }
 
+pub fn drawing(day: usize) -> Option<Drawing> {
+    match day {
+        10 => Some(day10::drawing),
+        16 => Some(day16::drawing),
+        17 => Some(day17::drawing),
+        _ => None,
+    }
+}
+
+pub fn diagram(day: usize) -> Option<Diagram> {
+    match day {
+        20 => Some(day20::diagram),
+        23 => Some(day23::diagram),
+        25 => Some(day25::diagram),
+        _ => None,
+    }
+}
+
+pub fn animation(day: usize) -> Option<Animation> {
+    match day {
+        14 => Some(day14::animation),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::fmt::Display;
@@ -1,12 +1,14 @@
 use itertools::Itertools;
 
+use crate::{number_theory::Integer, polynomial::quadratic_negative_range};
+
 type Race = (Number, Number);
-type Number = f64;
+type Number = Integer;
 
 pub fn first(input: &str) -> String {
     multiple_races(input)
         .map(number_of_ways_to_beat_record)
-        .product::<f64>()
+        .product::<Number>()
         .to_string()
 }
 
@@ -14,14 +16,10 @@ pub fn second(input: &str) -> String {
     number_of_ways_to_beat_record(single_race(input)).to_string()
 }
 
-fn number_of_ways_to_beat_record((time, distance): Race) -> f64 {
-    let (min, max) = roots(1.0, -time, distance);
-    max.ceil() - min.floor() - 1.0
-}
-
-fn roots(a: Number, b: Number, c: Number) -> (Number, Number) {
-    let d = b * b - 4.0 * a * c;
-    ((-b - d.sqrt()) / (2.0 * a), (-b + d.sqrt()) / (2.0 * a))
+/// Holding the button for `x` milliseconds beats the record if `x * (time - x) > distance`.
+fn number_of_ways_to_beat_record((time, distance): Race) -> Number {
+    let holding_times = quadratic_negative_range(1, -time, distance);
+    holding_times.end - holding_times.start
 }
 
 fn multiple_races(input: &str) -> impl Iterator<Item = Race> + '_ {
@@ -1,6 +1,10 @@
 use itertools::Itertools;
 
-use crate::HashMap;
+use crate::{
+    number_theory::{self, Integer},
+    search::cycle::{self, Cycle},
+    HashMap,
+};
 
 type Network<'input> = HashMap<Node<'input>, [Node<'input>; 2]>;
 type Connection<'input> = (Node<'input>, [Node<'input>; 2]);
@@ -19,25 +23,94 @@ pub fn second(input: &str) -> String {
     time_to_all_ghosts_at_destinations(&network, directions).to_string()
 }
 
+/// The times at which a ghost is at a destination, listed up to the end of the first cycle of its
+/// states, after which they repeat with the period of the cycle.
+struct Ghost {
+    destination_times: Vec<Time>,
+    cycle: Cycle,
+}
+
+impl Ghost {
+    fn new<'input>(network: &Network<'input>, directions: &[char], mut node: Node<'input>) -> Self {
+        let mut detector = cycle::Detector::default();
+        let mut destination_times = vec![];
+        for time in 0.. {
+            let direction_index = time % directions.len();
+            if let Some(cycle) = detector.observe((node, direction_index)) {
+                return Self {
+                    destination_times,
+                    cycle,
+                };
+            }
+            if is_destination(node) {
+                destination_times.push(time);
+            }
+            node = step(network, node, directions[direction_index]);
+        }
+        unreachable!("states of a ghost should repeat")
+    }
+
+    fn periodic_from(&self) -> Time {
+        let (start, length) = self.cycle;
+        start + length
+    }
+
+    fn is_at_destination(&self, time: Time) -> bool {
+        let (start, length) = self.cycle;
+        let time = if time < start + length {
+            time
+        } else {
+            start + (time - start) % length
+        };
+        self.destination_times.binary_search(&time).is_ok()
+    }
+
+    /// Returns a congruence `time ≡ residue (mod modulus)` for every time within the cycle at which
+    /// the ghost is at a destination.
+    fn congruences(&self) -> impl Iterator<Item = (Integer, Integer)> + Clone + '_ {
+        let (start, length) = self.cycle;
+        self.destination_times
+            .iter()
+            .filter(move |&&time| time >= start)
+            .map(move |&time| (integer(time), integer(length)))
+    }
+}
+
 fn time_to_all_ghosts_at_destinations(network: &Network, directions: &str) -> Time {
-    let mut ghosts = network
+    let directions = directions.chars().collect_vec();
+    let ghosts = network
         .keys()
         .filter(|node| is_starting(node))
-        .map(|node| destination_and_time(network, directions, 0, node))
+        .map(|node| Ghost::new(network, &directions, node))
         .collect_vec();
-    let mut destination_network = HashMap::new();
-    while !ghosts.iter().map(|(_, time)| *time).all_equal() {
-        let ghost = ghosts
-            .iter_mut()
-            .min_by_key(|(_, time)| *time)
-            .expect("there should be at least one ghost");
-        let direction_index = ghost.1 % directions.len();
-        let (destination, time) = *destination_network
-            .entry((ghost.0, direction_index))
-            .or_insert_with(|| destination_and_time(network, directions, direction_index, ghost.0));
-        *ghost = (destination, ghost.1 + time);
+    let periodic_from = ghosts
+        .iter()
+        .map(Ghost::periodic_from)
+        .max()
+        .expect("there should be at least one ghost");
+    let before_all_ghosts_are_periodic =
+        (0..periodic_from).find(|&time| ghosts.iter().all(|ghost| ghost.is_at_destination(time)));
+    if let Some(time) = before_all_ghosts_are_periodic {
+        return time;
     }
-    ghosts[0].1
+    let periodic_from = integer(periodic_from);
+    ghosts
+        .iter()
+        .map(Ghost::congruences)
+        .multi_cartesian_product()
+        .filter_map(number_theory::chinese_remainder)
+        .map(|(residue, modulus)| {
+            let number_of_periods = (periodic_from - residue + modulus - 1).div_euclid(modulus);
+            residue + number_of_periods.max(0) * modulus
+        })
+        .min()
+        .expect("all ghosts should be at destinations at the same time")
+        .try_into()
+        .expect("time should fit in usize")
+}
+
+fn integer(time: Time) -> Integer {
+    time.try_into().expect("time should fit in i128")
 }
 
 fn destination_and_time<'input>(
@@ -89,7 +162,7 @@ fn connection(connection: &str) -> Connection {
 
 #[cfg(test)]
 mod tests {
-    use super::super::tests::test_on_input;
+    use super::{super::tests::test_on_input, *};
     use crate::{Input, Puzzle};
 
     const DAY: usize = 8;
@@ -110,14 +183,32 @@ mod tests {
         test_on_input(DAY, Puzzle::Second, Input::Example(2), 6);
     }
 
-    // #[test]
-    // fn second_input() {
-    //     // This takes roughly three minutes with the 'dev' compilation profile
-    //     test_on_input(
-    //         DAY,
-    //         Puzzle::Second,
-    //         Input::PuzzleInput,
-    //         17_972_669_116_327_usize,
-    //     );
-    // }
+    #[test]
+    fn second_input() {
+        test_on_input(
+            DAY,
+            Puzzle::Second,
+            Input::PuzzleInput,
+            17_972_669_116_327_usize,
+        );
+    }
+
+    #[test]
+    fn ghosts_with_offset_cycles() {
+        let network = network(
+            "\
+            11A = (11B, XXX)\n\
+            11B = (11Z, XXX)\n\
+            11Z = (11C, XXX)\n\
+            11C = (11B, XXX)\n\
+            22A = (22Z, XXX)\n\
+            22Z = (22B, XXX)\n\
+            22B = (22C, XXX)\n\
+            22C = (22D, XXX)\n\
+            22D = (22Z, XXX)\n\
+            XXX = (XXX, XXX)\n\
+            ",
+        );
+        assert_eq!(time_to_all_ghosts_at_destinations(&network, "L"), 5);
+    }
 }
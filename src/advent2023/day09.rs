@@ -1,11 +1,5 @@
-use std::ops::{Add, Sub};
+use crate::{polynomial::ForwardDifferences, string::isizes};
 
-use itertools::Itertools;
-
-use crate::string::isizes;
-
-type Combination = fn(Number, Number) -> Number;
-type Prediction = fn(Number, Number) -> Number;
 type Number = isize;
 
 pub fn first(input: &str) -> String {
@@ -16,40 +10,27 @@ pub fn second(input: &str) -> String {
     sum_of_predictions(input, true).to_string()
 }
 
-fn sum_of_predictions(input: &str, reverse: bool) -> Number {
+fn sum_of_predictions(input: &str, backwards: bool) -> Number {
     input
         .lines()
-        .map(|history| prediction(history, reverse))
+        .map(|history| prediction(history, backwards))
         .sum()
 }
 
-fn prediction(history: &str, reverse: bool) -> Number {
-    let mut history = isizes(history);
-    if reverse {
-        history.reverse();
-    }
-    if reverse {
-        extrapolation(&history, Number::sub, Number::sub)
+fn prediction(history: &str, backwards: bool) -> Number {
+    let history = isizes(history);
+    let differences = ForwardDifferences::new(&history);
+    debug_assert!(
+        differences
+            .degree()
+            .map_or(true, |degree| degree + 1 < history.len()),
+        "history should determine its polynomial"
+    );
+    if backwards {
+        differences.evaluate(-1)
     } else {
-        extrapolation(&history, |left, right| right - left, Number::add)
-    }
-}
-
-fn extrapolation(history: &[Number], combination: Combination, prediction: Prediction) -> Number {
-    if history.iter().all(|&number| number == 0) {
-        return 0;
+        differences.evaluate(Number::try_from(history.len()).expect("history should be short"))
     }
-    let &last_number = history.last().expect("history should not be empty");
-    let succeeding_row = row(history, combination);
-    let succeeding_prediction = extrapolation(&succeeding_row, combination, prediction);
-    prediction(last_number, succeeding_prediction)
-}
-
-fn row(preceding_row: &[Number], combination: Combination) -> Vec<Number> {
-    preceding_row
-        .windows(2)
-        .map(|pair| combination(pair[0], pair[1]))
-        .collect_vec()
 }
 
 #[cfg(test)]
@@ -1,12 +1,12 @@
-use std::{f64, sync::OnceLock};
+use std::{cmp::Ordering, sync::OnceLock};
 
 use itertools::Itertools;
 
 use crate::{
-    floating_point::ApproxEq,
+    geometry::{self, LatticePolygon, Orientation},
     grid::{self, Direction, Grid, Position},
-    search::Exploration,
-    vector::{AngleInTwoDimensions, Negation, RotationInTwoDimensions, Vector},
+    image::{self, Image},
+    vector::{Negation, RotationInTwoDimensions, Vector},
     HashMap,
 };
 
@@ -18,44 +18,56 @@ pub fn first(input: &str) -> String {
 }
 
 pub fn second(input: &str) -> String {
-    area(&mut longest_cycle(&Grid::from(input))).to_string()
+    let grid = Grid::from(input);
+    area(&longest_cycle(&grid)).to_string()
 }
 
-pub fn area(cycle: &mut [Position]) -> usize {
-    if !is_clockwise(cycle) {
+pub fn drawing(input: &str) -> Image {
+    let grid = Grid::from(input);
+    let mut cycle = longest_cycle(&grid);
+    let interior = interior(&grid, &mut cycle);
+    let mut image = Image::new(&grid, |position, &tile| {
+        if interior[position] {
+            image::BLUE
+        } else if tile == '.' {
+            image::BLACK
+        } else {
+            image::DARK_GRAY
+        }
+    });
+    image.add_path(cycle.iter().copied().chain([cycle[0]]), image::YELLOW);
+    image.highlight([cycle[0]], image::RED);
+    image
+}
+
+pub fn area(cycle: &[Position]) -> isize {
+    LatticePolygon::new(cycle.iter().copied()).number_of_interior_points()
+}
+
+fn interior(grid: &Grid<Tile>, cycle: &mut [Position]) -> Grid<bool> {
+    if LatticePolygon::new(cycle.iter().copied()).orientation() != Orientation::Clockwise {
         cycle.reverse();
     }
 
-    let mut area = Exploration::new(cycle.iter().copied());
+    let mut is_on_cycle = grid.map(|_, _| false);
+    for &position in &*cycle {
+        is_on_cycle[position] = true;
+    }
+    let mut seeds = vec![];
     for (&first, &second, &third) in cycle.iter().circular_tuple_windows() {
         let [toward, away] = [second.sub(first), third.sub(second)];
-        let angle = toward.angle(away);
-        let directions_toward_inside = if angle.approx_eq(-f64::consts::FRAC_PI_2) {
-            vec![]
-        } else if angle.approx_eq(0.0) {
-            vec![away.right()]
-        } else if angle.approx_eq(f64::consts::FRAC_PI_2) {
-            vec![away.right(), away.neg()]
-        } else {
-            panic!("cycle should only curve left or right")
+        let directions_toward_inside = match geometry::cross(toward, away).cmp(&0) {
+            Ordering::Less => vec![],
+            Ordering::Equal => vec![away.right()],
+            Ordering::Greater => vec![away.right(), away.neg()],
         };
-        for direction in directions_toward_inside {
-            area.explore(second.add(direction), grid::neighbors);
-        }
+        seeds.extend(
+            directions_toward_inside
+                .into_iter()
+                .map(|direction| second.add(direction)),
+        );
     }
-    area.explored().len() - cycle.len()
-}
-
-fn is_clockwise(cycle: &[Position]) -> bool {
-    let angles_sum: f64 = cycle
-        .iter()
-        .circular_tuple_windows()
-        .map(|(&first, &second, &third)| {
-            let [toward, away] = [second.sub(first), third.sub(second)];
-            toward.angle(away)
-        })
-        .sum();
-    angles_sum.is_sign_negative()
+    is_on_cycle.flood_fill(seeds, |_, &is_on_cycle| !is_on_cycle)
 }
 
 fn longest_cycle(grid: &Grid<Tile>) -> Cycle {
@@ -1,12 +1,44 @@
 use itertools::Itertools;
 
 use crate::{
-    grid::{self, Direction, Grid, Position},
-    vector::Vector,
-    HashMap,
+    bit_grid::{BitGrid, BitRow},
+    grid::{self, Coordinate, Direction, Grid},
+    search::cycle,
+    simulation::Simulation,
 };
 
-type Platform = Grid<char>;
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Platform {
+    round_rocks: BitGrid,
+    cube_rocks: BitGrid,
+}
+
+impl<S: AsRef<str>> From<S> for Platform {
+    fn from(platform: S) -> Self {
+        let platform = Grid::<char>::from(platform);
+        Self {
+            round_rocks: BitGrid::from_grid(&platform, |_, &space| space == 'O'),
+            cube_rocks: BitGrid::from_grid(&platform, |_, &space| space == '#'),
+        }
+    }
+}
+
+impl Platform {
+    fn grid(&self) -> Grid<char> {
+        let [height, width] = [self.round_rocks.height(), self.round_rocks.width()];
+        Grid::new(height, width, |position| {
+            if self.round_rocks.get(position) == Some(true) {
+                'O'
+            } else if self.cube_rocks.get(position) == Some(true) {
+                '#'
+            } else {
+                '.'
+            }
+        })
+    }
+}
+
+const SPIN_CYCLE: [Direction; 4] = [grid::NORTH, grid::WEST, grid::SOUTH, grid::EAST];
 
 pub fn first(input: &str) -> String {
     let mut platform = Platform::from(input);
@@ -15,68 +47,106 @@ pub fn first(input: &str) -> String {
 }
 
 pub fn second(input: &str) -> String {
-    let mut platform = Platform::from(input);
-    let (cycle_start, cycle_length) = cycle_start_and_length(&mut platform);
-    let number_of_missing_cycles = (1_000_000_000 - cycle_start) % cycle_length;
-    cycles(&mut platform, number_of_missing_cycles);
+    let platform = cycle::state_after(
+        Platform::from(input),
+        |mut platform| {
+            cycles(&mut platform, 1);
+            platform
+        },
+        |platform| platform.round_rocks.clone(),
+        1_000_000_000,
+    );
     total_load(&platform).to_string()
 }
 
-fn total_load(platform: &Platform) -> usize {
-    (1..)
-        .zip(platform.rows().rev())
-        .map(|(index, row)| index * row.filter(|&&space| space == 'O').count())
-        .sum()
+pub fn animation(input: &str) -> Box<dyn Simulation> {
+    Box::new(SpinningPlatform {
+        platform: Platform::from(input),
+        number_of_tilts: 0,
+    })
 }
 
-fn cycle_start_and_length(platform: &mut Platform) -> (usize, usize) {
-    let mut previous = HashMap::new();
-    for number_of_cycles in 0_usize.. {
-        previous.insert(platform.clone(), number_of_cycles);
-        cycles(platform, 1);
-        if let Some(&cycle_start) = previous.get(platform) {
-            return (cycle_start, 1 + number_of_cycles - cycle_start);
-        }
+struct SpinningPlatform {
+    platform: Platform,
+    number_of_tilts: usize,
+}
+
+impl Simulation for SpinningPlatform {
+    fn frame(&self) -> Grid<char> {
+        self.platform.grid()
+    }
+
+    fn step(&mut self) -> bool {
+        let direction = SPIN_CYCLE[self.number_of_tilts % SPIN_CYCLE.len()];
+        tilt(&mut self.platform, direction);
+        self.number_of_tilts += 1;
+        true
     }
-    unreachable!("cycle should occur");
+}
+
+fn total_load(platform: &Platform) -> usize {
+    (1..)
+        .zip(platform.round_rocks.rows().iter().rev())
+        .map(|(index, row)| index * row.count_ones())
+        .sum()
 }
 
 fn cycles(platform: &mut Platform, number_of_cycles: usize) {
-    let directions = [grid::NORTH, grid::WEST, grid::SOUTH, grid::EAST];
     for _ in 0..number_of_cycles {
-        for direction in directions {
+        for direction in SPIN_CYCLE {
             tilt(platform, direction);
         }
     }
 }
 
+/// Tilts north and south by tilting the transposed platform west and east, which packs rows.
 fn tilt(platform: &mut Platform, direction: Direction) {
-    for mut rock_position in sorted_round_rock_positions(platform, direction) {
-        platform[rock_position] = '.';
-        loop {
-            let next_position = rock_position.add(direction);
-            if platform.get(next_position) != Some(&'.') {
-                break;
-            }
-            rock_position = next_position;
+    match direction {
+        grid::NORTH | grid::SOUTH => {
+            let mut transposed = Platform {
+                round_rocks: platform.round_rocks.transposed(),
+                cube_rocks: platform.cube_rocks.transposed(),
+            };
+            tilt_horizontally(&mut transposed, direction[0]);
+            platform.round_rocks = transposed.round_rocks.transposed();
         }
-        platform[rock_position] = 'O';
+        grid::WEST | grid::EAST => tilt_horizontally(platform, direction[1]),
+        _ => panic!("direction should be one of four unit vectors"),
     }
 }
 
-fn sorted_round_rock_positions(platform: &Platform, direction: Direction) -> Vec<Position> {
-    let platform_elements: Box<dyn Iterator<Item = (Position, &char)>> = match direction {
-        grid::NORTH | grid::SOUTH => Box::new(platform.iter_row_major()),
-        grid::WEST | grid::EAST => Box::new(platform.iter_column_major()),
-        _ => panic!("direction should be one of four unit vectors"),
-    };
-    let mut round_rock_positions = platform_elements
-        .filter_map(|(position, &element)| (element == 'O').then_some(position))
-        .collect_vec();
-    if [grid::SOUTH, grid::EAST].contains(&direction) {
-        round_rock_positions.reverse();
+fn tilt_horizontally(platform: &mut Platform, step: Coordinate) {
+    let width = platform.round_rocks.width();
+    let rows = platform
+        .round_rocks
+        .rows_mut()
+        .iter_mut()
+        .zip(platform.cube_rocks.rows());
+    for (round_rocks, cube_rocks) in rows {
+        *round_rocks = packed_row(round_rocks, cube_rocks, width, step);
+    }
+}
+
+/// Packs the round rocks between every two cube rocks of a row against one of them.
+fn packed_row(round_rocks: &BitRow, cube_rocks: &BitRow, width: usize, step: Coordinate) -> BitRow {
+    let mut packed = BitRow::new(width);
+    let mut round_columns = round_rocks.ones().peekable();
+    let mut start = 0;
+    for end in cube_rocks.ones().chain([width]) {
+        let count = round_columns
+            .peeking_take_while(|&column| column < end)
+            .count();
+        let columns = if step < 0 {
+            start..start + count
+        } else {
+            end - count..end
+        };
+        for column in columns {
+            packed.set(column, true);
+        }
+        start = end + 1;
     }
-    round_rock_positions
+    packed
 }
 
 #[cfg(test)]
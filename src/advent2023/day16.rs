@@ -1,11 +1,12 @@
 use std::collections::BTreeSet;
 
 use easy_cast::Cast;
-use itertools::Itertools;
 
 use crate::{
     grid::{self, Direction, Grid, Position},
+    image::{self, Image},
     vector::Vector,
+    HashSet,
 };
 
 type Beam = (Direction, Position);
@@ -21,7 +22,24 @@ pub fn second(input: &str) -> String {
     maximum_number_of_energized_tiles(&grid).to_string()
 }
 
+pub fn drawing(input: &str) -> Image {
+    let grid = Grid::from(input);
+    let energized_tiles = energized_tiles(&grid, (grid::EAST, [0, 0]));
+    Image::new(&grid, |position, &tile| {
+        match (energized_tiles.contains(&position), tile) {
+            (true, '.') => image::YELLOW,
+            (true, _) => image::WHITE,
+            (false, '.') => image::BLACK,
+            (false, _) => image::GRAY,
+        }
+    })
+}
+
 fn number_of_energized_tiles(grid: &Grid<char>, initial_beam: Beam) -> usize {
+    energized_tiles(grid, initial_beam).len()
+}
+
+fn energized_tiles(grid: &Grid<char>, initial_beam: Beam) -> HashSet<Position> {
     let mut energized: BTreeSet<Beam> = BTreeSet::new();
     let mut beams: Vec<Beam> = Vec::from([initial_beam]);
     while let Some(beam @ (direction, position)) = beams.pop() {
@@ -47,8 +65,7 @@ fn number_of_energized_tiles(grid: &Grid<char>, initial_beam: Beam) -> usize {
     energized
         .into_iter()
         .map(|(_, position)| position)
-        .counts()
-        .len()
+        .collect()
 }
 
 fn reflection_north_east_diagonal([row, column]: Direction) -> Direction {
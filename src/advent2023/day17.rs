@@ -1,10 +1,11 @@
 use std::ops::RangeInclusive;
 
-use easy_cast::Conv;
+use easy_cast::{Cast, Conv};
 
 use crate::{
     grid::{self, Coordinate, Direction, Grid, Position},
-    search::cheapest_path_cost,
+    image::{self, Image},
+    search::{astar_cost, cheapest_path, states_on_cheapest_paths},
     vector::{RotationInTwoDimensions, Vector},
 };
 
@@ -20,21 +21,60 @@ pub fn second(input: &str) -> String {
     minimum_heat_loss(&Map::from(input), 4..=10).to_string()
 }
 
+pub fn drawing(input: &str) -> Image {
+    let map = Map::from(input);
+    let machine_parts_factory = machine_parts_factory(&map);
+    let (_, route): (HeatLoss, _) = cheapest_path(
+        (None, [0, 0]),
+        |mov| moves(&map, mov, 1..=3),
+        |(_, position)| position == machine_parts_factory,
+    )
+    .expect("search should reach the machine parts factory");
+    let states_on_cheapest_paths = states_on_cheapest_paths(
+        (None, [0, 0]),
+        |mov| moves(&map, mov, 1..=3),
+        |(_, position)| position == machine_parts_factory,
+    );
+    let mut image = Image::new(&map, |_, &heat_loss| {
+        let brightness = u8::conv(heat_loss) * 25;
+        [brightness; 3]
+    });
+    image.highlight(
+        states_on_cheapest_paths
+            .into_iter()
+            .map(|(_, position)| position),
+        image::YELLOW,
+    );
+    image.add_path(route.into_iter().map(|(_, position)| position), image::RED);
+    image
+}
+
 fn minimum_heat_loss(map: &Map, number_of_steps: RangeInclusive<usize>) -> HeatLoss {
     let starting_point = (None, [0, 0]);
-    let machine_parts_factory = [
-        Coordinate::conv(map.height()) - 1,
-        Coordinate::conv(map.width()) - 1,
-    ];
+    let machine_parts_factory = machine_parts_factory(map);
     let is_machine_parts_factory = |(_, position)| position == machine_parts_factory;
-    cheapest_path_cost(
+    // Every block loses at least one unit of heat, so the remaining distance never overestimates.
+    let remaining_distance = |(_, position)| distance(position, machine_parts_factory);
+    astar_cost(
         starting_point,
         |mov| moves(map, mov, number_of_steps.clone()),
+        remaining_distance,
         is_machine_parts_factory,
     )
     .expect("search should reach the machine parts factory")
 }
 
+fn machine_parts_factory(map: &Map) -> Position {
+    [
+        Coordinate::conv(map.height()) - 1,
+        Coordinate::conv(map.width()) - 1,
+    ]
+}
+
+fn distance(position: Position, target: Position) -> HeatLoss {
+    target.sub(position).manhattan().cast()
+}
+
 fn moves(
     map: &Map,
     (previous_direction, position): Move,
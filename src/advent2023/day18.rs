@@ -1,6 +1,4 @@
-use itertools::Itertools;
-
-use crate::vector::Vector;
+use crate::{geometry::LatticePolygon, vector::Vector};
 
 type Area = isize;
 type Trench = Vec<Corner>;
@@ -22,17 +20,7 @@ pub fn second(input: &str) -> String {
 }
 
 fn area(trench: &[Corner]) -> Area {
-    let perimeter: Area = trench
-        .iter()
-        .circular_tuple_windows()
-        .map(|(&c0, &c1)| c1.sub(c0).norm())
-        .sum();
-    let areas = trench
-        .iter()
-        .circular_tuple_windows()
-        .map(|(&[x0, y0], &[x1, y1])| (y0 + y1) * (x0 - x1));
-    let area = (areas.sum::<Area>() / 2).abs();
-    area + perimeter / 2 + 1
+    LatticePolygon::new(trench.iter().copied()).number_of_enclosed_points()
 }
 
 fn trench(dig_plan: impl Iterator<Item = DigStep>) -> Trench {
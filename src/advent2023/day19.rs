@@ -1,8 +1,14 @@
-use std::{cmp::Ordering, ops::Range};
+use std::cmp::Ordering;
 
+use easy_cast::Cast;
 use itertools::Itertools;
 
-use crate::{string::usizes, HashMap};
+use crate::{
+    intervals::IntervalSet,
+    orthotope::{self, Orthotope},
+    string::usizes,
+    HashMap,
+};
 
 type Workflows<'input> = HashMap<Name<'input>, Rules<'input>>;
 type Workflow<'input> = (Name<'input>, Rules<'input>);
@@ -14,7 +20,7 @@ type Name<'input> = &'input str;
 type Part = [Rating; 4];
 
 type RatingRanges = [RatingRange; 4];
-type RatingRange = Range<Rating>;
+type RatingRange = IntervalSet<Rating>;
 
 pub fn first(input: &str) -> String {
     let (workflows, parts) = input
@@ -40,9 +46,7 @@ fn acceptable_rating_ranges(str: &str) -> Vec<RatingRanges> {
 }
 
 fn allowed_ranges() -> RatingRanges {
-    vec![(1..4001).clone(); 4]
-        .try_into()
-        .expect("'vec' should have correct length")
+    std::array::from_fn(|_| IntervalSet::from(1..4001))
 }
 
 fn acceptable_ranges(
@@ -60,8 +64,7 @@ fn acceptable_ranges(
     let mut acceptable_ranges = Vec::new();
     let (conditions, default) = &workflows[workflow_name];
     for &(category, comparison, limit, destination) in conditions {
-        let [true_range, false_range] =
-            split_range(possible_ranges[category].clone(), comparison, limit);
+        let [true_range, false_range] = split_range(&possible_ranges[category], comparison, limit);
         if !true_range.is_empty() {
             possible_ranges[category] = true_range;
             let acceptable_ranges_from_true_range =
@@ -79,17 +82,13 @@ fn acceptable_ranges(
     acceptable_ranges
 }
 
-fn split_range(range: RatingRange, comparison: Ordering, limit: Rating) -> [Range<Rating>; 2] {
-    let split = if comparison == Ordering::Less {
-        limit
+fn split_range(range: &RatingRange, comparison: Ordering, limit: Rating) -> [RatingRange; 2] {
+    let condition = if comparison == Ordering::Less {
+        IntervalSet::from(Rating::MIN..limit)
     } else {
-        limit + 1
+        IntervalSet::from(limit + 1..Rating::MAX)
     };
-    let mut split = [range.start..split, split..range.end];
-    if comparison == Ordering::Greater {
-        split.reverse();
-    }
-    split
+    [range.intersection(&condition), range.difference(&condition)]
 }
 
 fn acceptable_parts(
@@ -115,15 +114,27 @@ fn sum_of_ratings(parts: impl Iterator<Item = Part>) -> Rating {
 }
 
 fn number_of_combinations(ranges: &[RatingRanges]) -> usize {
+    let orthotopes = ranges.iter().flat_map(orthotopes).collect_vec();
+    orthotope::union_volume(&orthotopes).cast()
+}
+
+fn orthotopes(ranges: &RatingRanges) -> impl Iterator<Item = Orthotope<4>> + '_ {
     ranges
         .iter()
-        .map(|ranges| {
-            ranges
+        .map(|range| {
+            range
+                .intervals()
                 .iter()
-                .map(std::iter::ExactSizeIterator::len)
-                .product::<usize>()
+                .map(|interval| interval.start.cast()..interval.end.cast())
+        })
+        .multi_cartesian_product()
+        .map(|ranges| {
+            Orthotope::new(
+                ranges
+                    .try_into()
+                    .expect("there should be a range for each category"),
+            )
         })
-        .sum::<usize>()
 }
 
 fn workflows(str: &str) -> Workflows {
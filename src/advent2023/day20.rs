@@ -2,7 +2,7 @@ use std::collections::VecDeque;
 
 use itertools::Itertools;
 
-use crate::HashMap;
+use crate::{dot::Dot, graph::Graph, number_theory, HashMap};
 
 type Configuration<'input> = HashMap<&'input str, (Module<'input>, Vec<&'input str>)>;
 
@@ -23,28 +23,80 @@ pub fn first(input: &str) -> String {
     (total_number_of_low_pulses * total_number_of_high_pulses).to_string()
 }
 
-pub fn second(_input: &str) -> String {
-    unimplemented!()
+/// Assumes, as holds for the puzzle inputs, that `rx` is fed by a single conjunction module whose
+/// inputs each send it a high pulse periodically and independently of each other, first at the end
+/// of their first period. `rx` then receives a low pulse after the least common multiple of the
+/// periods. Other wirings get a wrong answer rather than an error.
+pub fn second(input: &str) -> String {
+    let mut configuration = configuration(input);
+    let wiring = wiring(&configuration);
+    let [last_conjunction] = wiring.predecessors("rx").collect_vec()[..] else {
+        panic!("a single conjunction module should send pulses to 'rx'");
+    };
+    let number_of_inputs = wiring.predecessors(last_conjunction).count();
+    let mut first_high_pulses = HashMap::new();
+    for number_of_presses in 1_usize.. {
+        for (source, pulse, destination) in press_button(&mut configuration) {
+            if pulse && destination == last_conjunction {
+                first_high_pulses.entry(source).or_insert(number_of_presses);
+            }
+        }
+        if first_high_pulses.len() == number_of_inputs {
+            break;
+        }
+    }
+    number_theory::lcm_all(first_high_pulses.into_values()).to_string()
+}
+
+pub fn diagram(input: &str) -> Dot {
+    let configuration = configuration(input);
+    let modules = configuration
+        .iter()
+        .sorted_unstable_by_key(|&(&name, _)| name)
+        .map(|(&name, (_, destinations))| (name, destinations.iter().copied()));
+    let mut dot = Dot::from_adjacency(
+        true,
+        modules,
+        |name| {
+            let (shape, color) = match configuration[name].0 {
+                Module::FlipFlop(_) => ("box", "lightblue"),
+                Module::Conjunction(_) => ("ellipse", "lightsalmon"),
+                Module::Broadcaster => ("diamond", "lightgreen"),
+            };
+            vec![
+                ("shape", shape.to_owned()),
+                ("style", "filled".to_owned()),
+                ("fillcolor", color.to_owned()),
+            ]
+        },
+        |_, destination| (destination, vec![]),
+    );
+    for destination in configuration
+        .values()
+        .flat_map(|(_, destinations)| destinations)
+        .filter(|destination| !configuration.contains_key(*destination))
+        .unique()
+    {
+        dot.add_node(destination, vec![("shape", "doublecircle".to_owned())]);
+    }
+    dot
 }
 
 fn total_number_of_low_and_high_pulses(configuration: &mut Configuration) -> (usize, usize) {
-    let (number_of_low_pulses, number_of_high_pulses): (Vec<_>, Vec<_>) =
-        (0..1000).map(|_| press_button(configuration)).unzip();
-    let total_number_of_low_pulses = number_of_low_pulses.into_iter().sum();
-    let total_number_of_high_pulses = number_of_high_pulses.into_iter().sum();
-    (total_number_of_low_pulses, total_number_of_high_pulses)
+    let pulses = (0..1000)
+        .flat_map(|_| press_button(configuration))
+        .collect_vec();
+    let number_of_high_pulses = pulses.iter().filter(|&&(_, pulse, _)| pulse).count();
+    (pulses.len() - number_of_high_pulses, number_of_high_pulses)
 }
 
-fn press_button(configuration: &mut Configuration) -> (usize, usize) {
-    let (mut number_of_low_pulses, mut number_of_high_pulses) = (0, 0);
+fn press_button<'input>(
+    configuration: &mut Configuration<'input>,
+) -> Vec<(&'input str, Pulse, &'input str)> {
+    let mut sent_pulses = vec![];
     let mut pulses = VecDeque::from([("button", LOW, "broadcaster")]);
     while let Some((source, pulse, destination)) = pulses.pop_front() {
-        if pulse {
-            number_of_high_pulses += 1;
-        } else {
-            number_of_low_pulses += 1;
-        }
-
+        sent_pulses.push((source, pulse, destination));
         if let Some((destination_module, output_pulse_destinations)) =
             configuration.get_mut(&destination)
         {
@@ -55,7 +107,7 @@ fn press_button(configuration: &mut Configuration) -> (usize, usize) {
             }
         }
     }
-    (number_of_low_pulses, number_of_high_pulses)
+    sent_pulses
 }
 
 fn receive(pulse: Pulse, to: &mut Module, from: &str) -> Option<Pulse> {
@@ -81,6 +133,17 @@ fn receive(pulse: Pulse, to: &mut Module, from: &str) -> Option<Pulse> {
     }
 }
 
+fn wiring<'input>(configuration: &Configuration<'input>) -> Graph<'input> {
+    configuration
+        .iter()
+        .flat_map(|(&source, (_, destinations))| {
+            destinations
+                .iter()
+                .map(move |&destination| (source, destination))
+        })
+        .collect()
+}
+
 fn configuration(str: &str) -> Configuration {
     let mut configuration: Configuration = str.lines().map(module).collect();
 
@@ -131,4 +194,14 @@ mod tests {
     fn first_input() {
         test_on_input(DAY, Puzzle::First, Input::PuzzleInput, 1_020_211_150);
     }
+
+    #[test]
+    fn second_input() {
+        test_on_input(
+            DAY,
+            Puzzle::Second,
+            Input::PuzzleInput,
+            238_815_727_638_557_usize,
+        );
+    }
 }
@@ -2,48 +2,81 @@ use std::mem;
 
 use crate::{
     grid::{self, Coordinate, Grid, Position},
+    polynomial::Polynomial,
+    rational::Rational,
     HashSet,
 };
 
 type Map = Grid<char>;
 
+const NUMBER_OF_STEPS_OF_SECOND_PUZZLE: usize = 26_501_365;
+
 pub fn first(input: &str) -> String {
-    let example = Map::from(input);
-    number_of_reachable_garden_plots(&example, 64).to_string()
+    let map = Map::from(input);
+    number_of_reachable_garden_plots(&map, 64).to_string()
+}
+
+pub fn second(input: &str) -> String {
+    let map = Map::from(input);
+    extrapolated_number_of_reachable_garden_plots(&map, NUMBER_OF_STEPS_OF_SECOND_PUZZLE)
+        .to_string()
 }
 
-pub fn second(_input: &str) -> String {
-    unimplemented!()
+/// Relies on the starting row and column of the map being free of rocks, so that the number of
+/// reachable plots grows quadratically with every further repetition of the map that is reached.
+fn extrapolated_number_of_reachable_garden_plots(map: &Map, number_of_steps: usize) -> i128 {
+    let period = map.height();
+    let samples = (0..3).map(|repetitions| {
+        let number_of_steps = number_of_steps % period + repetitions * period;
+        [
+            number_of_steps,
+            number_of_reachable_garden_plots(map, number_of_steps),
+        ]
+        .map(|value| i128::try_from(value).expect("sample should fit in i128"))
+    });
+    let polynomial = Polynomial::interpolate(samples.map(|[x, y]| (x, y)));
+    let number_of_steps = i128::try_from(number_of_steps).expect("number of steps should fit");
+    polynomial
+        .evaluate(Rational::from(number_of_steps))
+        .to_integer()
+        .expect("number of reachable garden plots should be an integer")
 }
 
+/// Counts the plots reachable in exactly `number_of_steps` steps on the map repeated infinitely in
+/// every direction.
 fn number_of_reachable_garden_plots(map: &Map, number_of_steps: usize) -> usize {
     let starting_plot = starting_plot(map);
-    let starting_plot_parity = position_parity(starting_plot);
+    let reachable_parity = (position_parity(starting_plot)
+        + Coordinate::try_from(number_of_steps % 2).expect("parity should fit"))
+        % 2;
 
     let mut explored = HashSet::from([starting_plot]);
     let mut frontier = vec![starting_plot];
     let mut next_frontier = vec![];
-    let mut is_current_distance_multiple_of_two_from_number_of_steps = number_of_steps % 2 == 0;
     let mut number_of_reachable_garden_plots = 0;
     for _ in 0..=number_of_steps {
         while let Some(plot) = frontier.pop() {
-            if position_parity(plot) == starting_plot_parity {
+            if position_parity(plot) == reachable_parity {
                 number_of_reachable_garden_plots += 1;
             }
 
             for neighbor in grid::neighbors(plot) {
-                if map.get(neighbor) == Some(&'.') && explored.insert(neighbor) {
+                if is_garden_plot(map, neighbor) && explored.insert(neighbor) {
                     next_frontier.push(neighbor);
                 }
             }
         }
         mem::swap(&mut frontier, &mut next_frontier);
-        is_current_distance_multiple_of_two_from_number_of_steps =
-            !is_current_distance_multiple_of_two_from_number_of_steps;
     }
     number_of_reachable_garden_plots
 }
 
+fn is_garden_plot(map: &Map, [row, column]: Position) -> bool {
+    let [height, width] = [map.height(), map.width()]
+        .map(|length| Coordinate::try_from(length).expect("map length should fit"));
+    map[[row.rem_euclid(height), column.rem_euclid(width)]] != '#'
+}
+
 fn starting_plot(map: &Map) -> Position {
     map.iter_row_major()
         .find_map(|(position, &tile)| (tile == 'S').then_some(position))
@@ -51,16 +84,18 @@ fn starting_plot(map: &Map) -> Position {
 }
 
 fn position_parity([row, column]: Position) -> Coordinate {
-    (row + column) % 2
+    (row + column).rem_euclid(2)
 }
 
 #[cfg(test)]
 mod tests {
+    use itertools::Itertools;
+
     use super::{
         super::tests::{test_on_input, YEAR},
         *,
     };
-    use crate::{input, Input, Puzzle};
+    use crate::{input, tests::test_cases, Input, Puzzle};
 
     const DAY: usize = 21;
 
@@ -76,15 +111,28 @@ mod tests {
         test_on_input(DAY, Puzzle::First, Input::PuzzleInput, 3642);
     }
 
-    // #[test]
-    // fn second_example() {
-    //     let map = Map::from(&input(YEAR, DAY, Input::Example(0)));
-    //     let cases = [6, 10, 50, 100, 500, 1000, 5000]
-    //         .into_iter()
-    //         .zip_eq([16, 50, 1594, 6536, 167_004, 668_697, 16_733_044]);
-    //     test_cases(
-    //         |number_of_steps| number_of_reachable_garden_plots(&map, number_of_steps),
-    //         cases,
-    //     );
-    // }
+    #[test]
+    fn second_example() {
+        let map = Map::from(&input(YEAR, DAY, Input::Example(0)));
+        // The example's starting row and column contain rocks, so the extrapolation does not apply
+        // and every case is counted step by step. That makes the example's 5000 steps, with
+        // 16_733_044 reachable garden plots, too slow for a unit test.
+        let cases = [6, 10, 50, 100, 500, 1000]
+            .into_iter()
+            .zip_eq([16, 50, 1594, 6536, 167_004, 668_697]);
+        test_cases(
+            |number_of_steps| number_of_reachable_garden_plots(&map, number_of_steps),
+            cases,
+        );
+    }
+
+    #[test]
+    fn second_input() {
+        test_on_input(
+            DAY,
+            Puzzle::Second,
+            Input::PuzzleInput,
+            608_603_023_105_276_u64,
+        );
+    }
 }
@@ -1,16 +1,14 @@
-use std::cmp;
-
 use easy_cast::Cast;
 use itertools::Itertools;
 
 use crate::{
+    dot::Dot,
+    graph::contract::{self, Contracted, Distance},
     grid::{self, Direction, Grid, Position},
-    vector::{Negation, Vector},
-    HashMap, HashSet,
+    search::longest_simple_path,
 };
 
-type Graph = HashMap<Position, Vec<(Position, Distance)>>;
-type Distance = usize;
+type Graph = Contracted<Position>;
 type Map = Grid<Tile>;
 type Tile = char;
 
@@ -24,6 +22,28 @@ pub fn second(input: &str) -> String {
     longest_hike(input, false).to_string()
 }
 
+pub fn diagram(input: &str) -> Dot {
+    let map = Map::from(input);
+    let goal = goal(&map);
+    let name = |[row, column]: Position| format!("{row},{column}");
+    let junctions = graph(&map)
+        .into_iter()
+        .sorted_unstable()
+        .map(|(position, successors)| (position, successors.into_iter().sorted_unstable()));
+    Dot::from_adjacency(
+        true,
+        junctions.map(|(position, successors)| (name(position), successors)),
+        |junction| {
+            if [name(START), name(goal)].contains(junction) {
+                vec![("shape", "doublecircle".to_owned())]
+            } else {
+                vec![]
+            }
+        },
+        |_, (successor, distance)| (name(successor), vec![("label", distance.to_string())]),
+    )
+}
+
 fn longest_hike(input: &str, slopes: bool) -> Distance {
     let mut map = Map::from(input);
     if !slopes {
@@ -31,90 +51,24 @@ fn longest_hike(input: &str, slopes: bool) -> Distance {
     }
     let graph = graph(&map);
     let (last_junction, distance_from_last_junction_to_goal) = last_junction(&graph, goal(&map));
-    let maximum_distance_to_last_junction = maximum_distance(&graph, START, last_junction);
+    let maximum_distance_to_last_junction = longest_simple_path(&graph, START, last_junction)
+        .expect("last junction should be reachable");
     maximum_distance_to_last_junction + distance_from_last_junction_to_goal
 }
 
-fn maximum_distance(graph: &Graph, from: Position, to: Position) -> Distance {
-    const STACK_MARKER: (Position, Distance) = ([0, 0], 0);
-    let mut maximum_distance = Distance::MIN;
-    let mut path = vec![];
-    let mut frontier = vec![(from, 0)];
-    while let Some((position, distance)) = frontier.pop() {
-        if position == STACK_MARKER.0 {
-            path.pop();
-            continue;
-        }
-
-        if position == to {
-            maximum_distance = cmp::max(maximum_distance, distance);
-            continue;
-        }
-
-        path.push(position);
-        frontier.push(STACK_MARKER);
-        for &(successor, step_distance) in &graph[&position] {
-            if !path.contains(&successor) {
-                frontier.push((successor, distance + step_distance));
-            }
-        }
-    }
-    maximum_distance
-}
-
 fn graph(map: &Map) -> Graph {
-    let mut explored = HashSet::from([(START, grid::SOUTH)]);
-    let mut frontier = vec![(START, grid::SOUTH)];
-    let mut graph = HashMap::new();
-    while let Some((from, toward)) = frontier.pop() {
-        if let Some((to, next_towards, distance)) = next_junction(map, from, toward) {
-            graph.entry(from).or_insert(vec![]).push((to, distance));
-            for next_toward in next_towards {
-                if explored.insert((to, next_toward)) {
-                    frontier.push((to, next_toward));
-                }
-            }
-        }
-    }
-    graph
-}
-
-fn next_junction(
-    map: &Map,
-    from: Position,
-    mut toward: Direction,
-) -> Option<(Position, impl Iterator<Item = Direction>, Distance)> {
-    let mut position = from.add(toward);
-
-    if ![None, Some(toward)].contains(&slope(map[position])) {
-        return None;
-    }
-
-    let mut distance = 1;
-    let mut next_path_tiles = next_path_tiles(map, toward.neg(), position);
-    while next_path_tiles.len() == 1 {
-        (position, toward) = next_path_tiles[0];
-        distance += 1;
-        next_path_tiles = self::next_path_tiles(map, toward.neg(), position);
-    }
-
-    if ![None, Some(toward)].contains(&slope(map[position.sub(toward)])) {
-        return None;
-    }
-
-    let next_towards = next_path_tiles.into_iter().map(|(_, direction)| direction);
-    Some((position, next_towards, distance))
-}
-
-fn next_path_tiles(map: &Map, from: Direction, position: Position) -> Vec<(Position, Direction)> {
-    grid::DIRECTIONS
-        .into_iter()
-        .filter(|&direction| direction != from)
-        .filter_map(move |direction| {
-            let neighbor = position.add(direction);
-            (![None, Some(&'#')].contains(&map.get(neighbor))).then_some((neighbor, direction))
-        })
-        .collect_vec()
+    let goal = goal(map);
+    let can_move = |&from: &Tile, direction, &to: &Tile| {
+        to != '#' && slope(from).map_or(true, |slope| slope == direction)
+    };
+    let is_junction = |position| {
+        let number_of_paths = grid::neighbors(position)
+            .into_iter()
+            .filter(|&neighbor| map.get(neighbor).is_some_and(|&tile| tile != '#'))
+            .count();
+        position == START || position == goal || number_of_paths > 2
+    };
+    contract::grid(map, [START], can_move, is_junction)
 }
 
 fn slope(tile: Tile) -> Option<Direction> {
@@ -152,11 +106,66 @@ fn goal(map: &Map) -> Position {
 
 #[cfg(test)]
 mod tests {
-    use super::super::tests::test_on_input;
+    use std::cmp;
+
+    use super::{
+        super::tests::{test_on_input, YEAR},
+        *,
+    };
     use crate::{Input, Puzzle};
 
     const DAY: usize = 23;
 
+    /// Enumerates every path, for verifying the faster longest simple path search.
+    fn maximum_distance(graph: &Graph, from: Position, to: Position) -> Distance {
+        const STACK_MARKER: (Position, Distance) = ([0, 0], 0);
+        let mut maximum_distance = Distance::MIN;
+        let mut path = vec![];
+        let mut frontier = vec![(from, 0)];
+        while let Some((position, distance)) = frontier.pop() {
+            if position == STACK_MARKER.0 {
+                path.pop();
+                continue;
+            }
+
+            if position == to {
+                maximum_distance = cmp::max(maximum_distance, distance);
+                continue;
+            }
+
+            path.push(position);
+            frontier.push(STACK_MARKER);
+            for &(successor, step_distance) in &graph[&position] {
+                if !path.contains(&successor) {
+                    frontier.push((successor, distance + step_distance));
+                }
+            }
+        }
+        maximum_distance
+    }
+
+    fn test_against_enumeration(input: Input, slopes: bool) {
+        let mut map = Map::from(crate::input(YEAR, DAY, input));
+        if !slopes {
+            map = map.map(|_, &tile| slope_to_path(tile));
+        }
+        let graph = graph(&map);
+        let goal = goal(&map);
+        for to in [goal, last_junction(&graph, goal).0] {
+            assert_eq!(
+                longest_simple_path(&graph, START, to),
+                Some(maximum_distance(&graph, START, to))
+            );
+        }
+    }
+
+    #[test]
+    fn longest_simple_path_matches_enumeration() {
+        test_against_enumeration(Input::Example(0), true);
+        test_against_enumeration(Input::Example(0), false);
+        test_against_enumeration(Input::PuzzleInput, true);
+    }
+
     #[test]
     fn first_example() {
         test_on_input(DAY, Puzzle::First, Input::Example(0), 94);
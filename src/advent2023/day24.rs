@@ -1,63 +1,53 @@
-use std::ops::RangeInclusive;
+use std::{array, ops::RangeInclusive};
 
 use itertools::Itertools;
+use num_traits::Zero;
 
-use crate::{linear_equations::solution_set, vector::Vector};
+use crate::{
+    geometry::Line,
+    linear_equations::{solution_set, Matrix},
+    rational::Rational,
+    vector::Vector,
+};
 
 type Hailstone = (Position, Velocity);
 type Position = [Coordinate; 3];
 type Velocity = [Coordinate; 3];
-type Coordinate = f64;
+type Coordinate = i128;
+type Fraction = Rational<Coordinate>;
 
 pub fn first(input: &str) -> String {
     let hailstones = hailstones(input);
-    let area = 200_000_000_000_000.0..=400_000_000_000_000.0;
+    let area = 200_000_000_000_000..=400_000_000_000_000;
     number_of_intersections_in_test_area(area, &hailstones).to_string()
 }
 
 pub fn second(input: &str) -> String {
     let hailstones = hailstones(input);
-    let (position, _) = initial_position_and_velocity(&hailstones);
-    position.into_iter().sum::<Coordinate>().round().to_string()
+    let rock @ (position, _) = initial_position_and_velocity(&hailstones);
+    assert!(
+        hits_every_hailstone(&hailstones, rock),
+        "rock should hit every hailstone"
+    );
+    position.into_iter().sum::<Coordinate>().to_string()
 }
 
 fn number_of_intersections_in_test_area(
     area: RangeInclusive<Coordinate>,
     hailstones: &[Hailstone],
 ) -> usize {
-    hailstones
+    let paths = hailstones
         .iter()
-        .combinations(2)
-        .filter_map(|pair| path_intersection(*pair[0], *pair[1]))
-        .filter(|&intersection| is_inside_area(area.clone(), intersection))
+        .map(|&([x, y, _], [vx, vy, _])| Line::ray([x, y], [vx, vy]))
+        .collect_vec();
+    let bounds = [area.clone(), area];
+    paths
+        .iter()
+        .tuple_combinations()
+        .filter(|(first, second)| first.intersects_within(second, &bounds))
         .count()
 }
 
-fn path_intersection((s0, v0): Hailstone, (s1, v1): Hailstone) -> Option<[Coordinate; 2]> {
-    const X: usize = 0;
-    const Y: usize = 1;
-    let augmented_matrix: [[Coordinate; 3]; 2] = [
-        [v0[X], -v1[X], s1[X] - s0[X]],
-        [v0[Y], -v1[Y], s1[Y] - s0[Y]],
-    ];
-    match solution_set(augmented_matrix) {
-        None => None,
-        Some((origin, directions)) if directions.is_empty() => {
-            let time = origin;
-            let collision_in_future_for_both_hailstones = time[0] >= 0.0 && time[1] >= 0.0;
-            collision_in_future_for_both_hailstones
-                .then(|| [s0[X] + v0[X] * time[0], s0[Y] + v0[Y] * time[0]])
-        }
-        _ => panic!("hailstones should not have identical paths"),
-    }
-}
-
-fn is_inside_area(range: RangeInclusive<Coordinate>, point: [Coordinate; 2]) -> bool {
-    point
-        .into_iter()
-        .all(|coordinate| range.contains(&coordinate))
-}
-
 /// Returns the initial position and velocity needed for the rock to hit every hailstone.
 ///
 /// # Correctness
@@ -99,19 +89,59 @@ fn is_inside_area(range: RangeInclusive<Coordinate>, point: [Coordinate; 2]) ->
 /// To bring these equations into standard matrix-equation form, we subtract the right-hand sides,
 /// which gives `(A[0] - A[1]) . v == 0` and `(A[0] - A[2]) . v == 0`. Finally, moving the constants
 /// to the now-empty right-hand sides yields a system of linear equations that can be solved.
+///
+/// The system is solved exactly with rationals. To keep them within `i128`, every position and
+/// velocity is first taken relative to the `0`'th hailstone, which shrinks the coefficients.
 fn initial_position_and_velocity(hailstones: &[Hailstone]) -> (Position, Velocity) {
-    let mut augmented_matrix: [[Coordinate; 7]; 6] = Default::default();
-    augmented_matrix[0..3].copy_from_slice(&equations(hailstones[0], hailstones[1]));
-    augmented_matrix[3..6].copy_from_slice(&equations(hailstones[0], hailstones[2]));
+    let (frame_position, frame_velocity) = hailstones[0];
+    let relative = |(position, velocity): Hailstone| {
+        (position.sub(frame_position), velocity.sub(frame_velocity))
+    };
+    let augmented_matrix: [[Fraction; 7]; 6] = [1, 2]
+        .into_iter()
+        .flat_map(|index| equations(relative(hailstones[0]), relative(hailstones[index])))
+        .map(|equation| equation.map(Fraction::from))
+        .collect_vec()
+        .try_into()
+        .expect("two pairs of hailstones should give six equations");
+    let integer = |fraction: Fraction| {
+        fraction
+            .to_integer()
+            .expect("rock should start at integer coordinates with integer velocity")
+    };
     match solution_set(augmented_matrix) {
         Some((origin, directions)) if directions.is_empty() => (
-            [origin[0], origin[1], origin[2]],
-            [origin[3], origin[4], origin[5]],
+            frame_position.add(array::from_fn(|axis| integer(origin[axis]))),
+            frame_velocity.add(array::from_fn(|axis| integer(origin[3 + axis]))),
         ),
         _ => panic!("linear equations should have exactly one solution"),
     }
 }
 
+/// Returns whether the rock hits every hailstone, by checking that its position and velocity
+/// satisfy the equations `(A[0] - A[i]) . v == 0` of every hailstone `i`, not only of the two that
+/// they were solved from.
+fn hits_every_hailstone(
+    hailstones: &[Hailstone],
+    (position, velocity): (Position, Velocity),
+) -> bool {
+    let augmented_matrix = Matrix::from_rows(
+        hailstones[1..]
+            .iter()
+            .flat_map(|&hailstone| equations(hailstones[0], hailstone))
+            .map(|equation| equation.map(Fraction::from)),
+    );
+    let unknowns = Matrix::from_rows(
+        position
+            .into_iter()
+            .chain(velocity)
+            .chain([-1])
+            .map(|coordinate| [Fraction::from(coordinate)]),
+    );
+    let residuals = &augmented_matrix * &unknowns;
+    (0..residuals.number_of_rows()).all(|row| residuals[[row, 0]].is_zero())
+}
+
 /// Let `s[i]` and `v[i]` be the initial position and velocity of the `i`'th hailstone. Then, let
 /// `A[i]` be the following matrix (notice the signs of the last column):
 ///
@@ -146,9 +176,9 @@ fn equations(left: Hailstone, right: Hailstone) -> [[Coordinate; 7]; 3] {
 /// ```
 fn coefficients(([sx, sy, sz], [vx, vy, vz]): Hailstone) -> [[Coordinate; 7]; 3] {
     [
-        [0.0, vz, -vy, 0.0, -sz, sy, vy * sz - vz * sy],
-        [-vz, 0.0, vx, sz, 0.0, -sx, vz * sx - vx * sz],
-        [vy, -vx, 0.0, -sy, sx, 0.0, vx * sy - vy * sx],
+        [0, vz, -vy, 0, -sz, sy, vy * sz - vz * sy],
+        [-vz, 0, vx, sz, 0, -sx, vz * sx - vx * sz],
+        [vy, -vx, 0, -sy, sx, 0, vx * sy - vy * sx],
     ]
 }
 
@@ -186,7 +216,7 @@ mod tests {
     #[test]
     fn first_example() {
         let hailstones = hailstones(&input(YEAR, DAY, Input::Example(0)));
-        let number_of_intersections = number_of_intersections_in_test_area(7.0..=27.0, &hailstones);
+        let number_of_intersections = number_of_intersections_in_test_area(7..=27, &hailstones);
         assert_eq!(number_of_intersections, 2);
     }
 
@@ -210,14 +240,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rock_hits_every_hailstone() {
+        let hailstones = hailstones(&input(YEAR, DAY, Input::Example(0)));
+        let rock = ([24, 13, 10], [-3, 1, 2]);
+        assert!(hits_every_hailstone(&hailstones, rock));
+        let wrong_rock = ([24, 13, 10], [-3, 1, 3]);
+        assert!(!hits_every_hailstone(&hailstones, wrong_rock));
+    }
+
     #[test]
     fn coefficients() {
-        let hailstone = ([20.0, 19.0, 15.0], [1.0, -5.0, -3.0]);
+        let hailstone = ([20, 19, 15], [1, -5, -3]);
         let actual = super::coefficients(hailstone);
         let expected = [
-            [0.0, -3.0, 5.0, 0.0, -15.0, 19.0, -18.0],
-            [3.0, 0.0, 1.0, 15.0, 0.0, -20.0, -75.0],
-            [-5.0, -1.0, 0.0, -19.0, 20.0, 0.0, 119.0],
+            [0, -3, 5, 0, -15, 19, -18],
+            [3, 0, 1, 15, 0, -20, -75],
+            [-5, -1, 0, -19, 20, 0, 119],
         ];
         assert_eq!(actual, expected);
     }
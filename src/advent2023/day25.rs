@@ -1,12 +1,12 @@
-use rand::{rngs::SmallRng, seq::IteratorRandom, SeedableRng};
+use itertools::Itertools;
 
 use crate::{
-    search::{shortest_path, Exploration},
+    dot::Dot,
+    graph::flow::{self, Node},
     HashMap, HashSet,
 };
 
 type Apparatus<'input> = HashMap<Component<'input>, HashSet<Component<'input>>>;
-type Connection<'input> = [Component<'input>; 2];
 type Component<'input> = &'input str;
 
 pub fn first(input: &str) -> String {
@@ -21,50 +21,82 @@ pub fn second(_input: &str) -> String {
     panic!("there is no second part on the 25th");
 }
 
-fn disconnected_group_sizes(apparatus: &Apparatus) -> [usize; 2] {
-    let mut rng = SmallRng::from_seed([0; 32]);
-    loop {
-        let terminals = apparatus.keys().copied().choose_multiple(&mut rng, 2);
-        let (source, target) = (terminals[0], |component| component == terminals[1]);
-        let mut paths: HashSet<Connection> = HashSet::new();
-        let mut number_of_disjoint_paths = 0;
-        loop {
-            let successors = |from| {
-                let paths = &paths;
-                apparatus[&from]
-                    .iter()
-                    .copied()
-                    .filter(move |&to| !paths.contains(&[from, to]))
+pub fn diagram(input: &str) -> Dot {
+    let apparatus = apparatus(input);
+    let (group, cut_wires) = minimum_cut(&apparatus);
+    let group: HashSet<Component> = group.into_iter().collect();
+    let cut_wires: HashSet<[Component; 2]> = cut_wires.into_iter().map(sorted).collect();
+    let connections = apparatus
+        .iter()
+        .sorted_unstable_by_key(|&(&component, _)| component)
+        .map(|(&component, connections)| (component, connections.iter().copied().sorted()));
+    Dot::from_adjacency(
+        false,
+        connections,
+        |component| {
+            let color = if group.contains(component) {
+                "lightblue"
+            } else {
+                "lightsalmon"
             };
-            let Some(path) = shortest_path(source, successors, target) else {
-                return [0, 1].map(|index| group_size(apparatus, &paths, terminals[index]));
+            vec![
+                ("style", "filled".to_owned()),
+                ("fillcolor", color.to_owned()),
+            ]
+        },
+        |&from, to| {
+            let attributes = if cut_wires.contains(&sorted([from, to])) {
+                vec![("color", "red".to_owned()), ("penwidth", "3".to_owned())]
+            } else {
+                vec![]
             };
-            for connection in path.windows(2) {
-                let [from, to] = [connection[0], connection[1]];
-                if paths.contains(&[to, from]) {
-                    paths.remove(&[to, from]);
-                } else {
-                    paths.insert([from, to]);
-                }
-            }
-            number_of_disjoint_paths += 1;
-            if number_of_disjoint_paths > 3 {
-                break;
-            }
-        }
-    }
+            (to, attributes)
+        },
+    )
 }
 
-fn group_size(apparatus: &Apparatus, paths: &HashSet<Connection>, component: Component) -> usize {
-    let successors = |from| {
-        apparatus[&from]
-            .iter()
-            .copied()
-            .filter(move |&to| !paths.contains(&[from, to]) && !paths.contains(&[to, from]))
-    };
-    let mut exploration = Exploration::new([]);
-    exploration.explore(component, successors);
-    exploration.explored().len()
+fn disconnected_group_sizes(apparatus: &Apparatus) -> [usize; 2] {
+    let (group, _) = minimum_cut(apparatus);
+    [group.len(), apparatus.len() - group.len()]
+}
+
+/// Returns the components on one side of a minimum cut and the wires that cross it.
+fn minimum_cut<'input>(
+    apparatus: &Apparatus<'input>,
+) -> (Vec<Component<'input>>, Vec<[Component<'input>; 2]>) {
+    let components = apparatus.keys().copied().collect_vec();
+    let nodes: HashMap<Component, Node> = components
+        .iter()
+        .enumerate()
+        .map(|(node, &component)| (component, node))
+        .collect();
+    let wires = apparatus.iter().flat_map(|(&from, tos)| {
+        let nodes = &nodes;
+        tos.iter()
+            .filter(move |&&to| from < to)
+            .map(move |&to| (nodes[from], nodes[to], 1_usize))
+    });
+    let cut = flow::global_minimum_cut(components.len(), wires)
+        .expect("apparatus should have at least two components");
+    let group = cut
+        .source_side
+        .iter()
+        .map(|&node| components[node])
+        .collect();
+    let cut_wires = cut
+        .edges
+        .iter()
+        .map(|edge| edge.map(|node| components[node]))
+        .collect();
+    (group, cut_wires)
+}
+
+fn sorted([from, to]: [Component; 2]) -> [Component; 2] {
+    if from <= to {
+        [from, to]
+    } else {
+        [to, from]
+    }
 }
 
 fn apparatus(input: &str) -> Apparatus {
@@ -0,0 +1,299 @@
+use std::iter;
+
+use easy_cast::Cast;
+use itertools::Itertools;
+
+use crate::grid::{Grid, Position};
+
+type Word = u64;
+
+const WORD_SIZE: usize = Word::BITS as usize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitRow {
+    words: Vec<Word>,
+    width: usize,
+}
+
+impl BitRow {
+    pub fn new(width: usize) -> Self {
+        Self {
+            words: vec![0; width.div_ceil(WORD_SIZE)],
+            width,
+        }
+    }
+
+    pub fn get(&self, column: usize) -> bool {
+        assert!(column < self.width, "column should be within row");
+        (self.words[column / WORD_SIZE] >> (column % WORD_SIZE)) & 1 == 1
+    }
+
+    pub fn set(&mut self, column: usize, value: bool) {
+        assert!(column < self.width, "column should be within row");
+        let mask = 1 << (column % WORD_SIZE);
+        if value {
+            self.words[column / WORD_SIZE] |= mask;
+        } else {
+            self.words[column / WORD_SIZE] &= !mask;
+        }
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones())
+            .sum::<u32>()
+            .cast()
+    }
+
+    /// Returns the columns of the set bits in ascending order.
+    pub fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(index, &word)| {
+            iter::successors((word != 0).then_some(word), |&word| {
+                let rest = word & (word - 1);
+                (rest != 0).then_some(rest)
+            })
+            .map(move |word| {
+                let bit: usize = word.trailing_zeros().cast();
+                index * WORD_SIZE + bit
+            })
+        })
+    }
+}
+
+// 2023 day 14 packs rows by counting the rocks between cube rocks, so these word-level
+// operations have no caller yet.
+#[allow(dead_code)]
+impl BitRow {
+    pub fn and(&self, rhs: &Self) -> Self {
+        self.zip_words(rhs, |left, right| left & right)
+    }
+
+    pub fn or(&self, rhs: &Self) -> Self {
+        self.zip_words(rhs, |left, right| left | right)
+    }
+
+    pub fn and_not(&self, rhs: &Self) -> Self {
+        self.zip_words(rhs, |left, right| left & !right)
+    }
+
+    /// Shifts every bit `amount` columns toward higher column indices, or toward lower column
+    /// indices if `amount` is negative. Bits shifted beyond either end of the row are discarded.
+    pub fn shifted(&self, amount: isize) -> Self {
+        let [word_shift, bit_shift] = [
+            amount.unsigned_abs() / WORD_SIZE,
+            amount.unsigned_abs() % WORD_SIZE,
+        ];
+        let word = |index: Option<usize>| {
+            index
+                .and_then(|index| self.words.get(index))
+                .copied()
+                .unwrap_or(0)
+        };
+        let words = (0..self.words.len())
+            .map(|index| {
+                if amount >= 0 {
+                    let near = word(index.checked_sub(word_shift));
+                    let far = word(index.checked_sub(word_shift + 1));
+                    let carry = if bit_shift == 0 {
+                        0
+                    } else {
+                        far >> (WORD_SIZE - bit_shift)
+                    };
+                    (near << bit_shift) | carry
+                } else {
+                    let near = word(Some(index + word_shift));
+                    let far = word(Some(index + word_shift + 1));
+                    let carry = if bit_shift == 0 {
+                        0
+                    } else {
+                        far << (WORD_SIZE - bit_shift)
+                    };
+                    (near >> bit_shift) | carry
+                }
+            })
+            .collect_vec();
+        let mut shifted = Self {
+            words,
+            width: self.width,
+        };
+        shifted.clear_padding();
+        shifted
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    fn zip_words(&self, rhs: &Self, operation: impl Fn(Word, Word) -> Word) -> Self {
+        debug_assert_eq!(self.width, rhs.width, "rows should have equal widths");
+        let words = self
+            .words
+            .iter()
+            .zip_eq(&rhs.words)
+            .map(|(&left, &right)| operation(left, right))
+            .collect_vec();
+        Self {
+            words,
+            width: self.width,
+        }
+    }
+
+    fn clear_padding(&mut self) {
+        let used_bits_in_last_word = self.width % WORD_SIZE;
+        if let (Some(last), true) = (self.words.last_mut(), used_bits_in_last_word != 0) {
+            *last &= (1 << used_bits_in_last_word) - 1;
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    rows: Vec<BitRow>,
+    width: usize,
+}
+
+impl BitGrid {
+    pub fn new(height: usize, width: usize) -> Self {
+        Self {
+            rows: vec![BitRow::new(width); height],
+            width,
+        }
+    }
+
+    pub fn from_grid<T>(grid: &Grid<T>, mut is_set: impl FnMut(Position, &T) -> bool) -> Self {
+        let mut bit_grid = Self::new(grid.height(), grid.width());
+        for (position, element) in grid.iter_row_major() {
+            if is_set(position, element) {
+                bit_grid.set(position, true);
+            }
+        }
+        bit_grid
+    }
+
+    pub fn get(&self, [row, column]: Position) -> Option<bool> {
+        let [row, column]: [usize; 2] = [row.try_into().ok()?, column.try_into().ok()?];
+        (column < self.width)
+            .then(|| self.rows.get(row).map(|bit_row| bit_row.get(column)))
+            .flatten()
+    }
+
+    pub fn set(&mut self, [row, column]: Position, value: bool) {
+        let [row, column]: [usize; 2] = [row.cast(), column.cast()];
+        self.rows[row].set(column, value);
+    }
+
+    /// Returns the grid mirrored along its main diagonal, whose rows are the columns of this grid.
+    pub fn transposed(&self) -> Self {
+        let mut transposed = Self::new(self.width, self.height());
+        for (row, bit_row) in self.rows.iter().enumerate() {
+            for column in bit_row.ones() {
+                transposed.rows[column].set(row, true);
+            }
+        }
+        transposed
+    }
+
+    pub fn rows(&self) -> &[BitRow] {
+        &self.rows
+    }
+
+    pub fn rows_mut(&mut self) -> &mut [BitRow] {
+        &mut self.rows
+    }
+
+    // Loads are weighted by row, so 2023 day 14 counts the rocks of every row separately.
+    #[allow(dead_code)]
+    pub fn count_ones(&self) -> usize {
+        self.rows.iter().map(BitRow::count_ones).sum()
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(bits: &str) -> BitRow {
+        let mut row = BitRow::new(bits.len());
+        for (column, bit) in bits.chars().enumerate() {
+            row.set(column, bit == '1');
+        }
+        row
+    }
+
+    fn bits(row: &BitRow) -> String {
+        (0..row.width)
+            .map(|column| if row.get(column) { '1' } else { '0' })
+            .collect()
+    }
+
+    #[test]
+    fn bitwise_operations() {
+        let [left, right] = [row("1100"), row("1010")];
+        assert_eq!(bits(&left.and(&right)), "1000");
+        assert_eq!(bits(&left.or(&right)), "1110");
+        assert_eq!(bits(&left.and_not(&right)), "0100");
+    }
+
+    #[test]
+    fn shifts_within_word() {
+        let row = row("1100101");
+        assert_eq!(bits(&row.shifted(2)), "0011001");
+        assert_eq!(bits(&row.shifted(-2)), "0010100");
+        assert_eq!(bits(&row.shifted(0)), "1100101");
+        assert_eq!(bits(&row.shifted(7)), "0000000");
+    }
+
+    #[test]
+    fn shifts_across_words() {
+        let mut wide = BitRow::new(150);
+        for column in [0, 63, 64, 149] {
+            wide.set(column, true);
+        }
+        let east = wide.shifted(70);
+        assert_eq!(east.ones().collect_vec(), vec![70, 133, 134]);
+        let west = wide.shifted(-64);
+        assert_eq!(west.ones().collect_vec(), vec![0, 85]);
+        assert_eq!(wide.shifted(1).ones().collect_vec(), vec![1, 64, 65]);
+    }
+
+    #[test]
+    fn population_count() {
+        let mut wide = BitRow::new(130);
+        for column in (0..130).step_by(3) {
+            wide.set(column, true);
+        }
+        assert_eq!(wide.count_ones(), 44);
+        assert!(!wide.is_empty());
+        assert!(BitRow::new(130).is_empty());
+    }
+
+    #[test]
+    fn transposition() {
+        let grid = Grid::<char>::from("#..#\n.#..\n..#.\n");
+        let bit_grid = BitGrid::from_grid(&grid, |_, &element| element == '#');
+        let transposed = bit_grid.transposed();
+        assert_eq!([transposed.height(), transposed.width()], [4, 3]);
+        assert_eq!(transposed.count_ones(), bit_grid.count_ones());
+        for ([row, column], _) in grid.iter_row_major() {
+            assert_eq!(transposed.get([column, row]), bit_grid.get([row, column]));
+        }
+        assert_eq!(transposed.transposed(), bit_grid);
+    }
+
+    #[test]
+    fn equal_grids_hash_equally() {
+        let grid = Grid::<char>::from("#..\n.#.\n");
+        let [left, right] = [0, 1].map(|_| BitGrid::from_grid(&grid, |_, &element| element == '#'));
+        let set = [left, right].into_iter().collect::<crate::HashSet<_>>();
+        assert_eq!(set.len(), 1);
+    }
+}
@@ -0,0 +1,149 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::HashSet;
+
+pub type Attributes = Vec<(&'static str, String)>;
+
+/// A Graphviz graph in the DOT language. Nodes and edges carry attributes such as `label`,
+/// `shape` or `color`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Dot {
+    is_directed: bool,
+    nodes: Vec<(String, Attributes)>,
+    edges: Vec<(String, String, Attributes)>,
+}
+
+impl Dot {
+    pub fn new(is_directed: bool) -> Self {
+        Self {
+            is_directed,
+            ..Self::default()
+        }
+    }
+
+    pub fn add_node(&mut self, name: impl Display, attributes: Attributes) {
+        self.nodes.push((name.to_string(), attributes));
+    }
+
+    pub fn add_edge(&mut self, from: impl Display, to: impl Display, attributes: Attributes) {
+        self.edges
+            .push((from.to_string(), to.to_string(), attributes));
+    }
+
+    /// Builds a graph from adjacency lists. Every node is styled by `node_attributes`, and `edge`
+    /// maps every element of an adjacency list to the neighbor it leads to and its attributes. An
+    /// undirected graph gets one edge per pair of adjacent nodes, even if the adjacency lists
+    /// contain both directions.
+    pub fn from_adjacency<Node, Edges, Neighbor>(
+        is_directed: bool,
+        adjacency: impl IntoIterator<Item = (Node, Edges)>,
+        mut node_attributes: impl FnMut(&Node) -> Attributes,
+        mut edge: impl FnMut(&Node, Edges::Item) -> (Neighbor, Attributes),
+    ) -> Self
+    where
+        Node: Display,
+        Edges: IntoIterator,
+        Neighbor: Display,
+    {
+        let mut dot = Self::new(is_directed);
+        let mut undirected_edges = HashSet::new();
+        for (node, edges) in adjacency {
+            dot.add_node(&node, node_attributes(&node));
+            for item in edges {
+                let (neighbor, attributes) = edge(&node, item);
+                let [from, to] = [node.to_string(), neighbor.to_string()];
+                let mut pair = [from.clone(), to.clone()];
+                pair.sort_unstable();
+                let is_duplicate = !is_directed && !undirected_edges.insert(pair);
+                if !is_duplicate {
+                    dot.add_edge(from, to, attributes);
+                }
+            }
+        }
+        dot
+    }
+}
+
+impl Display for Dot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (graph, edge_operator) = if self.is_directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        writeln!(f, "{graph} {{")?;
+        for (name, attributes) in &self.nodes {
+            writeln!(f, "    {}{};", quoted(name), AttributeList(attributes))?;
+        }
+        for (from, to, attributes) in &self.edges {
+            writeln!(
+                f,
+                "    {} {edge_operator} {}{};",
+                quoted(from),
+                quoted(to),
+                AttributeList(attributes)
+            )?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+struct AttributeList<'attributes>(&'attributes Attributes);
+
+impl Display for AttributeList<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return Ok(());
+        }
+        let attributes = self
+            .0
+            .iter()
+            .map(|(key, value)| format!("{key}={}", quoted(value)))
+            .collect::<Vec<_>>();
+        write!(f, " [{}]", attributes.join(", "))
+    }
+}
+
+fn quoted(str: &str) -> String {
+    format!("\"{}\"", str.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directed_graph_with_attributes() {
+        let mut dot = Dot::new(true);
+        dot.add_node("a", vec![("shape", "box".to_owned())]);
+        dot.add_node("b \"quoted\"", vec![]);
+        dot.add_edge("a", "b \"quoted\"", vec![("label", "3".to_owned())]);
+        let expected = "\
+            digraph {\n    \
+                \"a\" [shape=\"box\"];\n    \
+                \"b \\\"quoted\\\"\";\n    \
+                \"a\" -> \"b \\\"quoted\\\"\" [label=\"3\"];\n\
+            }\n";
+        assert_eq!(dot.to_string(), expected);
+    }
+
+    #[test]
+    fn undirected_adjacency_has_one_edge_per_pair() {
+        let adjacency = [("a", vec!["b", "c"]), ("b", vec!["a"]), ("c", vec!["a"])];
+        let dot = Dot::from_adjacency(
+            false,
+            adjacency,
+            |_| vec![],
+            |_, neighbor| (neighbor, vec![]),
+        );
+        let expected = "\
+            graph {\n    \
+                \"a\";\n    \
+                \"b\";\n    \
+                \"c\";\n    \
+                \"a\" -- \"b\";\n    \
+                \"a\" -- \"c\";\n\
+            }\n";
+        assert_eq!(dot.to_string(), expected);
+    }
+}
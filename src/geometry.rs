@@ -0,0 +1,136 @@
+pub mod line;
+
+use itertools::Itertools;
+
+pub use self::line::Line;
+use crate::number_theory::gcd;
+
+pub type Coordinate = isize;
+pub type Point = [Coordinate; 2];
+
+/// The direction in which the vertices of a polygon are traversed, with the first coordinate
+/// pointing right and the second up. Equivalently, with the first coordinate pointing down and the
+/// second right, as for grid positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    Clockwise,
+    Counterclockwise,
+    Degenerate,
+}
+
+/// A simple polygon whose vertices lie on integer points, in the order in which they are connected.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LatticePolygon {
+    vertices: Vec<Point>,
+}
+
+impl LatticePolygon {
+    pub fn new(vertices: impl IntoIterator<Item = Point>) -> Self {
+        Self {
+            vertices: vertices.into_iter().collect(),
+        }
+    }
+
+    // 2023 day 10 draws its loop from the cycle it built the polygon from.
+    #[allow(dead_code)]
+    pub fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+
+    /// Returns twice the signed area, which is an integer and positive for counterclockwise
+    /// polygons.
+    pub fn twice_signed_area(&self) -> Coordinate {
+        self.vertices
+            .iter()
+            .circular_tuple_windows()
+            .map(|(&from, &to)| cross(from, to))
+            .sum()
+    }
+
+    /// Returns the area rounded down, which is exact if the area is an integer.
+    // Pick's theorem in 2023 days 10 and 18 needs the exact doubled area, not a rounded one.
+    #[allow(dead_code)]
+    pub fn area(&self) -> Coordinate {
+        self.twice_signed_area().abs() / 2
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        match self.twice_signed_area().signum() {
+            1 => Orientation::Counterclockwise,
+            -1 => Orientation::Clockwise,
+            _ => Orientation::Degenerate,
+        }
+    }
+
+    /// Returns the number of integer points on the edges.
+    pub fn number_of_boundary_points(&self) -> Coordinate {
+        self.vertices
+            .iter()
+            .circular_tuple_windows()
+            .map(|(&[x0, y0], &[x1, y1])| gcd(x1 - x0, y1 - y0))
+            .sum()
+    }
+
+    /// Returns the number of integer points strictly inside, using Pick's theorem.
+    pub fn number_of_interior_points(&self) -> Coordinate {
+        (self.twice_signed_area().abs() - self.number_of_boundary_points() + 2) / 2
+    }
+
+    /// Returns the number of integer points inside or on the edges.
+    pub fn number_of_enclosed_points(&self) -> Coordinate {
+        self.number_of_interior_points() + self.number_of_boundary_points()
+    }
+}
+
+/// Returns the z-component of the cross product, which is positive if `to` is counterclockwise
+/// from `from`.
+pub fn cross([x0, y0]: Point, [x1, y1]: Point) -> Coordinate {
+    x0 * y1 - y0 * x1
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn triangle() {
+        let triangle = LatticePolygon::new([[0, 0], [4, 0], [0, 3]]);
+        assert_eq!(triangle.twice_signed_area(), 12);
+        assert_eq!(triangle.area(), 6);
+        assert_eq!(triangle.orientation(), Orientation::Counterclockwise);
+        assert_eq!(triangle.number_of_boundary_points(), 8);
+        assert_eq!(triangle.number_of_interior_points(), 3);
+        let reversed = LatticePolygon::new(triangle.vertices().iter().rev().copied());
+        assert_eq!(reversed.twice_signed_area(), -12);
+        assert_eq!(reversed.orientation(), Orientation::Clockwise);
+        assert_eq!(reversed.number_of_interior_points(), 3);
+        let line = LatticePolygon::new([[0, 0], [2, 2]]);
+        assert_eq!(line.orientation(), Orientation::Degenerate);
+    }
+
+    #[test]
+    fn rectangles_match_counting_points() {
+        let mut rng = SmallRng::seed_from_u64(46);
+        for _ in 0..100 {
+            let [x0, y0] = [0, 1].map(|_| rng.gen_range(-10..10));
+            let [x1, y1] = [x0 + rng.gen_range(1..10), y0 + rng.gen_range(1..10)];
+            let rectangle = LatticePolygon::new([[x0, y0], [x1, y0], [x1, y1], [x0, y1]]);
+            let points = (x0..=x1).flat_map(|x| (y0..=y1).map(move |y| [x, y]));
+            let interior = points
+                .clone()
+                .filter(|&[x, y]| x0 < x && x < x1 && y0 < y && y < y1)
+                .count();
+            let enclosed = points.count();
+            assert_eq!(
+                rectangle.number_of_interior_points(),
+                Coordinate::try_from(interior).expect("count should fit"),
+            );
+            assert_eq!(
+                rectangle.number_of_enclosed_points(),
+                Coordinate::try_from(enclosed).expect("count should fit"),
+            );
+        }
+    }
+}
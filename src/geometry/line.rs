@@ -0,0 +1,338 @@
+use std::{array, ops::RangeInclusive};
+
+use num_traits::{PrimInt, Signed, Zero};
+
+use crate::{linear_equations::solution_set, rational::Rational};
+
+/// How the infinite lines through two linear objects relate to each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Relation {
+    /// The lines meet in exactly one point.
+    Intersecting,
+    /// The lines are parallel but distinct.
+    Parallel,
+    /// The lines are identical.
+    Collinear,
+    /// The lines neither meet nor are parallel, which is only possible in three or more dimensions.
+    Skew,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Intersection<T, const N: usize> {
+    Disjoint,
+    Point([Rational<T>; N]),
+    /// The objects are collinear and share more than one point.
+    Overlap,
+}
+
+/// A line, ray or segment in `N` dimensions through integer points, whose intersections are exact.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Line<T, const N: usize> {
+    point: [T; N],
+    direction: [T; N],
+    /// The values of `t` for which `point + direction * t` belongs to the object.
+    extent: Parameters<T>,
+}
+
+impl<T: PrimInt + Signed, const N: usize> Line<T, N> {
+    // The hailstones of 2023 day 24 only move forward in time, so they are rays.
+    #[allow(dead_code)]
+    pub fn infinite(point: [T; N], direction: [T; N]) -> Self {
+        let extent = Parameters {
+            start: None,
+            end: None,
+        };
+        Self::new(point, direction, extent)
+    }
+
+    pub fn ray(point: [T; N], direction: [T; N]) -> Self {
+        let extent = Parameters {
+            start: Some(Rational::zero()),
+            end: None,
+        };
+        Self::new(point, direction, extent)
+    }
+
+    // No puzzle has needed lines with two ends yet.
+    #[allow(dead_code)]
+    pub fn segment(from: [T; N], to: [T; N]) -> Self {
+        let extent = Parameters::between(Rational::zero(), Rational::from(T::one()));
+        Self::new(from, array::from_fn(|axis| to[axis] - from[axis]), extent)
+    }
+
+    fn new(point: [T; N], direction: [T; N], extent: Parameters<T>) -> Self {
+        assert!(
+            direction.iter().any(|coordinate| !coordinate.is_zero()),
+            "direction should not be zero"
+        );
+        Self {
+            point,
+            direction,
+            extent,
+        }
+    }
+
+    pub fn point_at(&self, t: Rational<T>) -> [Rational<T>; N] {
+        array::from_fn(|axis| {
+            Rational::from(self.point[axis]) + Rational::from(self.direction[axis]) * t
+        })
+    }
+
+    // Parallel and skew hailstone paths both count as missing each other in 2023 day 24.
+    #[allow(dead_code)]
+    pub fn relation(&self, other: &Self) -> Relation {
+        match self.meeting(other) {
+            Meeting::At(..) => Relation::Intersecting,
+            Meeting::Everywhere => Relation::Collinear,
+            Meeting::Nowhere if self.is_parallel_to(other) => Relation::Parallel,
+            Meeting::Nowhere => Relation::Skew,
+        }
+    }
+
+    // `intersects_within` clips the common part to the test area of 2023 day 24 instead.
+    #[allow(dead_code)]
+    pub fn intersection(&self, other: &Self) -> Intersection<T, N> {
+        let common = self.common_parameters(other);
+        if common.is_empty() {
+            Intersection::Disjoint
+        } else if let Some(t) = common.single_value() {
+            Intersection::Point(self.point_at(t))
+        } else {
+            Intersection::Overlap
+        }
+    }
+
+    /// Returns whether the objects share at least one point within the box given by the inclusive
+    /// range of every axis.
+    pub fn intersects_within(&self, other: &Self, bounds: &[RangeInclusive<T>; N]) -> bool {
+        let mut common = self.common_parameters(other);
+        for (axis, range) in bounds.iter().enumerate() {
+            let [start, end, point, direction] = [
+                *range.start(),
+                *range.end(),
+                self.point[axis],
+                self.direction[axis],
+            ]
+            .map(Rational::from);
+            common = if direction.is_zero() {
+                if (start..=end).contains(&point) {
+                    common
+                } else {
+                    Parameters::empty()
+                }
+            } else {
+                let [from, to] = [start, end].map(|bound| (bound - point) / direction);
+                common.intersection(&Parameters::between(from, to))
+            };
+        }
+        !common.is_empty()
+    }
+
+    /// Solves `self.point + self.direction * t == other.point + other.direction * u` for the lines.
+    fn meeting(&self, other: &Self) -> Meeting<T> {
+        let augmented_matrix: [[Rational<T>; 3]; N] = array::from_fn(|axis| {
+            [
+                self.direction[axis],
+                -other.direction[axis],
+                other.point[axis] - self.point[axis],
+            ]
+            .map(Rational::from)
+        });
+        match solution_set(augmented_matrix) {
+            Some((origin, directions)) if directions.is_empty() => {
+                Meeting::At(origin[0], origin[1])
+            }
+            Some(_) => Meeting::Everywhere,
+            None => Meeting::Nowhere,
+        }
+    }
+
+    fn is_parallel_to(&self, other: &Self) -> bool {
+        (0..N).all(|axis| {
+            (0..N).all(|other_axis| {
+                self.direction[axis] * other.direction[other_axis]
+                    == self.direction[other_axis] * other.direction[axis]
+            })
+        })
+    }
+
+    /// Returns the parameters `t` of the points of `self` that also belong to `other`.
+    fn common_parameters(&self, other: &Self) -> Parameters<T> {
+        match self.meeting(other) {
+            Meeting::At(t, u) => {
+                if self.extent.contains(t) && other.extent.contains(u) {
+                    Parameters::between(t, t)
+                } else {
+                    Parameters::empty()
+                }
+            }
+            Meeting::Everywhere => {
+                // Every point `other.point + other.direction * u` lies on `self` at `t == offset +
+                // scale * u`, where `scale` is non-zero because the directions are parallel.
+                let squared_length = Rational::from(dot(self.direction, self.direction));
+                let offset = Rational::from(dot(
+                    array::from_fn(|axis| other.point[axis] - self.point[axis]),
+                    self.direction,
+                )) / squared_length;
+                let scale = Rational::from(dot(other.direction, self.direction)) / squared_length;
+                let [start, end] = [other.extent.start, other.extent.end]
+                    .map(|bound| bound.map(|u| offset + scale * u));
+                let mapped = if scale < Rational::zero() {
+                    Parameters {
+                        start: end,
+                        end: start,
+                    }
+                } else {
+                    Parameters { start, end }
+                };
+                self.extent.intersection(&mapped)
+            }
+            Meeting::Nowhere => Parameters::empty(),
+        }
+    }
+}
+
+/// The parameters `(t, u)` at which two lines meet.
+enum Meeting<T> {
+    At(Rational<T>, Rational<T>),
+    Everywhere,
+    Nowhere,
+}
+
+fn dot<T: PrimInt, const N: usize>(left: [T; N], right: [T; N]) -> T {
+    left.into_iter()
+        .zip(right)
+        .fold(T::zero(), |sum, (left, right)| sum + left * right)
+}
+
+/// A closed interval of parameters, which is unbounded where a bound is `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Parameters<T> {
+    start: Option<Rational<T>>,
+    end: Option<Rational<T>>,
+}
+
+impl<T: PrimInt + Signed> Parameters<T> {
+    fn between(from: Rational<T>, to: Rational<T>) -> Self {
+        Self {
+            start: Some(from.min(to)),
+            end: Some(from.max(to)),
+        }
+    }
+
+    fn empty() -> Self {
+        let [zero, one] = [T::zero(), T::one()].map(Rational::from);
+        Self {
+            start: Some(one),
+            end: Some(zero),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        matches!((self.start, self.end), (Some(start), Some(end)) if start > end)
+    }
+
+    fn contains(&self, value: Rational<T>) -> bool {
+        self.start.map_or(true, |start| start <= value) && self.end.map_or(true, |end| value <= end)
+    }
+
+    fn single_value(&self) -> Option<Rational<T>> {
+        self.start.filter(|&start| Some(start) == self.end)
+    }
+
+    fn intersection(&self, other: &Self) -> Self {
+        let start = match (self.start, other.start) {
+            (Some(left), Some(right)) => Some(left.max(right)),
+            (left, right) => left.or(right),
+        };
+        let end = match (self.end, other.end) {
+            (Some(left), Some(right)) => Some(left.min(right)),
+            (left, right) => left.or(right),
+        };
+        Self { start, end }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Fraction = Rational<i64>;
+
+    fn point<const N: usize>(coordinates: [i64; N]) -> [Fraction; N] {
+        coordinates.map(Fraction::from)
+    }
+
+    #[test]
+    fn segments_in_two_dimensions() {
+        let diagonal = Line::segment([0, 0], [4, 4]);
+        let crossing = Line::segment([0, 3], [3, 0]);
+        assert_eq!(diagonal.relation(&crossing), Relation::Intersecting);
+        assert_eq!(
+            diagonal.intersection(&crossing),
+            Intersection::Point([Fraction::new(3, 2); 2])
+        );
+        let short = Line::segment([0, 3], [1, 2]);
+        assert_eq!(diagonal.relation(&short), Relation::Intersecting);
+        assert_eq!(diagonal.intersection(&short), Intersection::Disjoint);
+        let touching = Line::segment([4, 4], [6, 2]);
+        assert_eq!(
+            diagonal.intersection(&touching),
+            Intersection::Point(point([4, 4]))
+        );
+    }
+
+    #[test]
+    fn parallel_and_collinear() {
+        let diagonal = Line::segment([0, 0], [4, 4]);
+        let parallel = Line::infinite([0, 1], [2, 2]);
+        assert_eq!(diagonal.relation(&parallel), Relation::Parallel);
+        assert_eq!(diagonal.intersection(&parallel), Intersection::Disjoint);
+        let overlapping = Line::segment([6, 6], [3, 3]);
+        assert_eq!(diagonal.relation(&overlapping), Relation::Collinear);
+        assert_eq!(diagonal.intersection(&overlapping), Intersection::Overlap);
+        let adjacent = Line::ray([4, 4], [1, 1]);
+        assert_eq!(
+            diagonal.intersection(&adjacent),
+            Intersection::Point(point([4, 4]))
+        );
+        let separate = Line::ray([5, 5], [1, 1]);
+        assert_eq!(diagonal.intersection(&separate), Intersection::Disjoint);
+        let opposite = Line::ray([-1, -1], [-1, -1]);
+        assert_eq!(diagonal.intersection(&opposite), Intersection::Disjoint);
+    }
+
+    #[test]
+    fn rays_and_lines_in_three_dimensions() {
+        let axis = Line::infinite([0, 0, 0], [0, 0, 1]);
+        let ray = Line::ray([1, 0, 5], [-1, 0, 0]);
+        assert_eq!(
+            axis.intersection(&ray),
+            Intersection::Point(point([0, 0, 5]))
+        );
+        let behind = Line::ray([1, 0, 5], [1, 0, 0]);
+        assert_eq!(axis.relation(&behind), Relation::Intersecting);
+        assert_eq!(axis.intersection(&behind), Intersection::Disjoint);
+        let skew = Line::infinite([1, 0, 0], [0, 1, 0]);
+        assert_eq!(axis.relation(&skew), Relation::Skew);
+        assert_eq!(axis.intersection(&skew), Intersection::Disjoint);
+    }
+
+    #[test]
+    fn intersection_within_box() {
+        let bounds = [0..=2, 0..=2];
+        let [first, second] = [Line::ray([0, 0], [1, 1]), Line::ray([0, 2], [1, -1])];
+        assert!(first.intersects_within(&second, &bounds));
+        let [first, second] = [Line::ray([0, 0], [1, 1]), Line::ray([0, 6], [1, -1])];
+        assert!(!first.intersects_within(&second, &bounds));
+        let [first, second] = [Line::ray([-5, -5], [1, 1]), Line::ray([9, 9], [-1, -1])];
+        assert!(first.intersects_within(&second, &bounds));
+        let [first, second] = [Line::ray([-5, 1], [1, 0]), Line::ray([9, 1], [1, 0])];
+        assert!(!first.intersects_within(&second, &bounds));
+        let [first, second] = [
+            Line::infinite([3, 0], [0, 1]),
+            Line::infinite([3, 1], [0, 1]),
+        ];
+        assert!(!first.intersects_within(&second, &bounds));
+    }
+}
@@ -0,0 +1,298 @@
+pub mod contract;
+pub mod flow;
+
+use itertools::Itertools;
+
+use crate::HashMap;
+
+/// A directed graph whose nodes are string slices borrowed from the puzzle input. Every name is
+/// interned once, so that edges refer to nodes by index.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Graph<'input> {
+    names: Vec<&'input str>,
+    indices: HashMap<&'input str, usize>,
+    successors: Vec<Vec<usize>>,
+    predecessors: Vec<Vec<usize>>,
+}
+
+impl<'input> Graph<'input> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_node(&mut self, name: &'input str) -> usize {
+        *self.indices.entry(name).or_insert_with(|| {
+            self.names.push(name);
+            self.successors.push(vec![]);
+            self.predecessors.push(vec![]);
+            self.names.len() - 1
+        })
+    }
+
+    pub fn add_edge(&mut self, from: &'input str, to: &'input str) {
+        let [from, to] = [self.add_node(from), self.add_node(to)];
+        self.successors[from].push(to);
+        self.predecessors[to].push(from);
+    }
+
+    // 2023 day 20 reaches every module through `wiring.predecessors` instead.
+    #[allow(dead_code)]
+    pub fn nodes(&self) -> impl Iterator<Item = &'input str> + '_ {
+        self.names.iter().copied()
+    }
+
+    // Module configurations keep their own destination lists, so only predecessors are looked up.
+    #[allow(dead_code)]
+    pub fn successors(&self, name: &str) -> impl Iterator<Item = &'input str> + '_ {
+        self.neighbors(&self.successors, name)
+    }
+
+    pub fn predecessors(&self, name: &str) -> impl Iterator<Item = &'input str> + '_ {
+        self.neighbors(&self.predecessors, name)
+    }
+
+    fn neighbors<'graph>(
+        &'graph self,
+        adjacency: &'graph [Vec<usize>],
+        name: &str,
+    ) -> impl Iterator<Item = &'input str> + 'graph {
+        let neighbors = self
+            .indices
+            .get(name)
+            .map_or(&[][..], |&index| &adjacency[index]);
+        neighbors.iter().map(|&index| self.names[index])
+    }
+
+    /// Orders the nodes so that every edge points forward, or returns the nodes of a cycle in
+    /// the order of its edges if there is none.
+    pub fn topological_order(&self) -> Result<Vec<&'input str>, Vec<&'input str>> {
+        let mut in_degrees = self.predecessors.iter().map(Vec::len).collect_vec();
+        let mut ready = (0..self.names.len())
+            .filter(|&index| in_degrees[index] == 0)
+            .collect_vec();
+        let mut order = vec![];
+        while let Some(index) = ready.pop() {
+            order.push(self.names[index]);
+            for &successor in &self.successors[index] {
+                in_degrees[successor] -= 1;
+                if in_degrees[successor] == 0 {
+                    ready.push(successor);
+                }
+            }
+        }
+        if order.len() == self.names.len() {
+            return Ok(order);
+        }
+
+        let is_remaining = |index: usize| in_degrees[index] > 0;
+        let mut positions = HashMap::new();
+        let mut walk = vec![];
+        let mut current = (0..self.names.len())
+            .find(|&index| is_remaining(index))
+            .expect("some node should remain when there is a cycle");
+        while !positions.contains_key(&current) {
+            positions.insert(current, walk.len());
+            walk.push(current);
+            current = *self.predecessors[current]
+                .iter()
+                .find(|&&predecessor| is_remaining(predecessor))
+                .expect("every remaining node should have a remaining predecessor");
+        }
+        let cycle = walk[positions[&current]..]
+            .iter()
+            .rev()
+            .map(|&index| self.names[index])
+            .collect();
+        Err(cycle)
+    }
+
+    /// Finds the strongly connected components with Tarjan's algorithm. Components are listed in
+    /// reverse topological order: no edge leads from a component to one listed after it.
+    // 2023 day 20 relies on the documented shape of its wiring rather than splitting it into cycles.
+    #[allow(dead_code)]
+    pub fn strongly_connected_components(&self) -> Vec<Vec<&'input str>> {
+        let number_of_nodes = self.names.len();
+        let mut indices: Vec<Option<usize>> = vec![None; number_of_nodes];
+        let mut low_links = vec![0; number_of_nodes];
+        let mut is_on_stack = vec![false; number_of_nodes];
+        let mut stack = vec![];
+        let mut components = vec![];
+        let mut next_index = 0;
+        for root in 0..number_of_nodes {
+            if indices[root].is_some() {
+                continue;
+            }
+            let mut calls = vec![(root, 0)];
+            while let Some((node, edge)) = calls.pop() {
+                if edge == 0 {
+                    indices[node] = Some(next_index);
+                    low_links[node] = next_index;
+                    next_index += 1;
+                    stack.push(node);
+                    is_on_stack[node] = true;
+                } else {
+                    let child = self.successors[node][edge - 1];
+                    low_links[node] = low_links[node].min(low_links[child]);
+                }
+
+                let unvisited = self.successors[node][edge..]
+                    .iter()
+                    .position(|&successor| {
+                        if let Some(index) = indices[successor] {
+                            if is_on_stack[successor] {
+                                low_links[node] = low_links[node].min(index);
+                            }
+                            false
+                        } else {
+                            true
+                        }
+                    })
+                    .map(|offset| edge + offset);
+                if let Some(unvisited) = unvisited {
+                    calls.push((node, unvisited + 1));
+                    calls.push((self.successors[node][unvisited], 0));
+                } else if Some(low_links[node]) == indices[node] {
+                    let mut component = vec![];
+                    loop {
+                        let member = stack.pop().expect("stack should contain the component");
+                        is_on_stack[member] = false;
+                        component.push(self.names[member]);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+        components
+    }
+}
+
+impl<'input> FromIterator<(&'input str, &'input str)> for Graph<'input> {
+    fn from_iter<T: IntoIterator<Item = (&'input str, &'input str)>>(edges: T) -> Self {
+        let mut graph = Self::new();
+        for (from, to) in edges {
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    use super::*;
+    use crate::HashSet;
+
+    const NAMES: [&str; 8] = ["a", "b", "c", "d", "e", "f", "g", "h"];
+
+    fn random_graph(rng: &mut SmallRng) -> Graph<'static> {
+        let mut graph = Graph::new();
+        for &name in &NAMES[..rng.gen_range(1..=NAMES.len())] {
+            graph.add_node(name);
+        }
+        let number_of_nodes = graph.names.len();
+        for _ in 0..rng.gen_range(0..=12) {
+            let [from, to] = [0, 1].map(|_| NAMES[rng.gen_range(0..number_of_nodes)]);
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+
+    fn reachable<'input>(graph: &Graph<'input>, from: &'input str) -> HashSet<&'input str> {
+        let mut reachable = HashSet::from([from]);
+        let mut frontier = vec![from];
+        while let Some(name) = frontier.pop() {
+            for successor in graph.successors(name) {
+                if reachable.insert(successor) {
+                    frontier.push(successor);
+                }
+            }
+        }
+        reachable
+    }
+
+    #[test]
+    fn predecessors_and_successors() {
+        let graph = Graph::from_iter([("a", "c"), ("b", "c"), ("c", "d")]);
+        assert_eq!(graph.predecessors("c").collect_vec(), vec!["a", "b"]);
+        assert_eq!(graph.successors("c").collect_vec(), vec!["d"]);
+        assert_eq!(graph.predecessors("x").count(), 0);
+    }
+
+    #[test]
+    fn topological_order_or_cycle() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..300 {
+            let graph = random_graph(&mut rng);
+            match graph.topological_order() {
+                Ok(order) => {
+                    assert_eq!(
+                        order.len(),
+                        graph.names.len(),
+                        "order should contain every node"
+                    );
+                    let position = |name| order.iter().position(|&other| other == name);
+                    for from in graph.nodes() {
+                        for to in graph.successors(from) {
+                            assert!(position(from) < position(to), "{from} should precede {to}");
+                        }
+                    }
+                }
+                Err(cycle) => {
+                    assert!(!cycle.is_empty(), "cycle should contain a node");
+                    for (index, &from) in cycle.iter().enumerate() {
+                        let to = cycle[(index + 1) % cycle.len()];
+                        assert!(
+                            graph.successors(from).contains(&to),
+                            "cycle should contain edge from {from} to {to}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn strongly_connected_components_are_mutually_reachable() {
+        let mut rng = SmallRng::seed_from_u64(1);
+        for _ in 0..300 {
+            let graph = random_graph(&mut rng);
+            let components = graph.strongly_connected_components();
+            let mut component_of = HashMap::new();
+            for (index, component) in components.iter().enumerate() {
+                for &name in component {
+                    assert!(
+                        component_of.insert(name, index).is_none(),
+                        "every node should be in exactly one component"
+                    );
+                }
+            }
+            assert_eq!(component_of.len(), graph.names.len());
+
+            let reachability: HashMap<&str, HashSet<&str>> = graph
+                .nodes()
+                .map(|name| (name, reachable(&graph, name)))
+                .collect();
+            for from in graph.nodes() {
+                for to in graph.nodes() {
+                    let is_mutual =
+                        reachability[from].contains(to) && reachability[to].contains(from);
+                    assert_eq!(
+                        component_of[from] == component_of[to],
+                        is_mutual,
+                        "{from} and {to} should share a component iff mutually reachable"
+                    );
+                    if reachability[from].contains(to) {
+                        assert!(
+                            component_of[from] >= component_of[to],
+                            "components should be in reverse topological order"
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
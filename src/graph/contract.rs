@@ -0,0 +1,194 @@
+use std::hash::Hash;
+
+use itertools::Itertools;
+
+use crate::{
+    grid::{self, Direction, Grid, Position},
+    search,
+    vector::Vector,
+    HashMap, HashSet,
+};
+
+pub type Contracted<Node> = HashMap<Node, Vec<(Node, Distance)>>;
+pub type Distance = usize;
+
+/// Contracts the graph reachable from `sources` by following corridors: from every source and
+/// every kept node, each successor is followed while the current node is not kept and has
+/// exactly one successor other than the node it was entered from. A corridor that ends in a kept
+/// node becomes a weighted edge; corridors ending anywhere else are dropped.
+pub fn corridors<Node, Successors>(
+    sources: impl IntoIterator<Item = Node>,
+    mut successors: impl FnMut(Node) -> Successors,
+    mut keep: impl FnMut(Node) -> bool,
+) -> Contracted<Node>
+where
+    Node: Copy + Eq + Hash,
+    Successors: IntoIterator<Item = Node>,
+{
+    let mut contracted = HashMap::new();
+    let mut frontier = sources.into_iter().collect_vec();
+    while let Some(from) = frontier.pop() {
+        if contracted.contains_key(&from) {
+            continue;
+        }
+        let mut edges = vec![];
+        for first in successors(from).into_iter().collect_vec() {
+            let corridor = corridor_end(from, first, &mut successors, &mut keep);
+            if let Some((to, distance)) = corridor.filter(|&(to, _)| to != from) {
+                edges.push((to, distance));
+                frontier.push(to);
+            }
+        }
+        contracted.insert(from, edges);
+    }
+    contracted
+}
+
+fn corridor_end<Node, Successors>(
+    from: Node,
+    first: Node,
+    successors: &mut impl FnMut(Node) -> Successors,
+    keep: &mut impl FnMut(Node) -> bool,
+) -> Option<(Node, Distance)>
+where
+    Node: Copy + Eq + Hash,
+    Successors: IntoIterator<Item = Node>,
+{
+    let [mut previous, mut current] = [from, first];
+    let mut visited = HashSet::from([from]);
+    let mut distance = 1;
+    while !keep(current) {
+        if !visited.insert(current) {
+            return None;
+        }
+        let mut onward = successors(current)
+            .into_iter()
+            .filter(|&next| next != previous);
+        let (Some(next), None) = (onward.next(), onward.next()) else {
+            return None;
+        };
+        [previous, current] = [current, next];
+        distance += 1;
+    }
+    Some((current, distance))
+}
+
+/// Contracts a grid maze into corridors between kept positions, where `can_move` decides whether
+/// a step in the given direction from one element to a neighboring element is allowed. Moves can
+/// be restricted to one direction, as on slopes.
+pub fn grid<T>(
+    grid: &Grid<T>,
+    sources: impl IntoIterator<Item = Position>,
+    mut can_move: impl FnMut(&T, Direction, &T) -> bool,
+    keep: impl FnMut(Position) -> bool,
+) -> Contracted<Position> {
+    let successors = |position: Position| {
+        let from = &grid[position];
+        grid::DIRECTIONS
+            .into_iter()
+            .filter_map(|direction| {
+                let neighbor = position.add(direction);
+                let to = grid.get(neighbor)?;
+                can_move(from, direction, to).then_some(neighbor)
+            })
+            .collect_vec()
+    };
+    corridors(sources, successors, keep)
+}
+
+/// Contracts the graph reachable from `sources` to its kept nodes, with an edge from every source
+/// and kept node to every other reachable kept node, weighted by the length of a shortest path.
+pub fn distances<Node, Successors>(
+    sources: impl IntoIterator<Item = Node>,
+    mut successors: impl FnMut(Node) -> Successors,
+    mut keep: impl FnMut(Node) -> bool,
+) -> Contracted<Node>
+where
+    Node: Copy + Eq + Hash,
+    Successors: IntoIterator<Item = Node>,
+{
+    let mut contracted = HashMap::new();
+    let mut frontier = sources.into_iter().collect_vec();
+    while let Some(from) = frontier.pop() {
+        if contracted.contains_key(&from) {
+            continue;
+        }
+        let edges = search::distances(from, &mut successors)
+            .into_iter()
+            .filter(|&(to, _)| to != from && keep(to))
+            .collect_vec();
+        frontier.extend(edges.iter().map(|&(to, _)| to));
+        contracted.insert(from, edges);
+    }
+    contracted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(contracted: &Contracted<Position>, node: Position) -> Vec<(Position, Distance)> {
+        contracted[&node].iter().copied().sorted().collect()
+    }
+
+    const MAZE: &str = "\
+        #.#####\n\
+        #.....#\n\
+        #.###.#\n\
+        #.....#\n\
+        #####.#\n";
+
+    #[test]
+    fn grid_maze_corridors() {
+        let maze = Grid::<char>::from(MAZE);
+        let [start, goal] = [[0, 1], [4, 5]];
+        let keep = |position| [start, [1, 1], [3, 5], goal].contains(&position);
+        let contracted = grid(&maze, [start], |_, _, &to| to != '#', keep);
+        assert_eq!(contracted.len(), 4);
+        assert_eq!(sorted(&contracted, start), vec![([1, 1], 1)]);
+        assert_eq!(
+            sorted(&contracted, [1, 1]),
+            vec![([0, 1], 1), ([3, 5], 6), ([3, 5], 6)]
+        );
+        assert_eq!(
+            sorted(&contracted, [3, 5]),
+            vec![([1, 1], 6), ([1, 1], 6), ([4, 5], 1)]
+        );
+    }
+
+    #[test]
+    fn one_way_corridors() {
+        let maze = Grid::<char>::from(MAZE.replace("#.....#\n#####", "#...>.#\n#####"));
+        let can_move = |&from: &char, direction, &to: &char| {
+            to != '#' && (from != '>' || direction == grid::EAST)
+        };
+        let keep = |position| [[0, 1], [1, 1], [3, 5], [4, 5]].contains(&position);
+        let contracted = grid(&maze, [[0, 1]], can_move, keep);
+        assert_eq!(
+            sorted(&contracted, [1, 1]),
+            vec![([0, 1], 1), ([3, 5], 6), ([3, 5], 6)]
+        );
+        assert_eq!(sorted(&contracted, [3, 5]), vec![([1, 1], 6), ([4, 5], 1)]);
+    }
+
+    #[test]
+    fn shortest_distances_between_kept_nodes() {
+        let edges = HashMap::from([
+            ('a', vec!['b']),
+            ('b', vec!['a', 'c', 'd']),
+            ('c', vec!['b', 'e']),
+            ('d', vec!['b']),
+            ('e', vec!['c']),
+        ]);
+        let contracted = distances(
+            ['a'],
+            |node| edges[&node].clone(),
+            |node| ['c', 'e'].contains(&node),
+        );
+        let sorted = |node| contracted[&node].iter().copied().sorted().collect_vec();
+        assert_eq!(contracted.len(), 3);
+        assert_eq!(sorted('a'), vec![('c', 2), ('e', 3)]);
+        assert_eq!(sorted('c'), vec![('e', 1)]);
+        assert_eq!(sorted('e'), vec![('c', 1)]);
+    }
+}
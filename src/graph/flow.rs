@@ -0,0 +1,456 @@
+use std::{
+    collections::{BinaryHeap, VecDeque},
+    iter,
+    ops::{Add, Sub},
+};
+
+use crate::HashMap;
+
+pub type Node = usize;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cut<Capacity> {
+    pub capacity: Capacity,
+    pub source_side: Vec<Node>,
+    pub edges: Vec<[Node; 2]>,
+}
+
+/// A flow network with nodes `0..number_of_nodes`. Every edge is stored together with its reverse
+/// edge, so that edge `index ^ 1` is the reverse of edge `index`.
+#[derive(Debug, Clone)]
+pub struct FlowNetwork<Capacity> {
+    heads: Vec<Node>,
+    capacities: Vec<Capacity>,
+    residuals: Vec<Capacity>,
+    adjacency: Vec<Vec<usize>>,
+}
+
+impl<Capacity> FlowNetwork<Capacity>
+where
+    Capacity: Copy + Ord + Default + Add<Output = Capacity> + Sub<Output = Capacity>,
+{
+    pub fn new(number_of_nodes: usize) -> Self {
+        Self {
+            heads: vec![],
+            capacities: vec![],
+            residuals: vec![],
+            adjacency: vec![vec![]; number_of_nodes],
+        }
+    }
+
+    // 2023 day 25 only needs undirected edges.
+    #[allow(dead_code)]
+    pub fn add_edge(&mut self, from: Node, to: Node, capacity: Capacity) {
+        self.add_edge_pair(from, to, capacity, Capacity::default());
+    }
+
+    pub fn add_undirected_edge(&mut self, from: Node, to: Node, capacity: Capacity) {
+        self.add_edge_pair(from, to, capacity, capacity);
+    }
+
+    fn add_edge_pair(&mut self, from: Node, to: Node, capacity: Capacity, reverse: Capacity) {
+        for (tail, head, capacity) in [(from, to, capacity), (to, from, reverse)] {
+            self.adjacency[tail].push(self.heads.len());
+            self.heads.push(head);
+            self.capacities.push(capacity);
+            self.residuals.push(capacity);
+        }
+    }
+
+    /// Computes a maximum flow from `source` to `sink` with Dinic's algorithm, starting from any
+    /// flow already pushed through the network. There is no flow from a node to itself.
+    pub fn max_flow(&mut self, source: Node, sink: Node) -> Capacity {
+        let mut flow = Capacity::default();
+        if source == sink {
+            return flow;
+        }
+        while let Some(levels) = self.levels(source, sink) {
+            let mut next_edges = vec![0; self.adjacency.len()];
+            while let Some(path) = self.augmenting_path(source, sink, &levels, &mut next_edges) {
+                let bottleneck = path
+                    .iter()
+                    .map(|&edge| self.residuals[edge])
+                    .min()
+                    .expect("augmenting path should contain at least one edge");
+                for edge in path {
+                    self.residuals[edge] = self.residuals[edge] - bottleneck;
+                    self.residuals[edge ^ 1] = self.residuals[edge ^ 1] + bottleneck;
+                }
+                flow = flow + bottleneck;
+            }
+        }
+        flow
+    }
+
+    // `global_minimum_cut` only reads the residual graph of its cheapest cut, so it skips this.
+    #[allow(dead_code)]
+    pub fn minimum_cut(&mut self, source: Node, sink: Node) -> Cut<Capacity> {
+        let capacity = self.max_flow(source, sink);
+        let is_source_side = self.residual_reachable(source);
+        let edges = (0..self.heads.len())
+            .filter(|&edge| self.capacities[edge] > Capacity::default())
+            .map(|edge| [self.heads[edge ^ 1], self.heads[edge]])
+            .filter(|&[from, to]| is_source_side[from] && !is_source_side[to])
+            .collect();
+        let source_side = (0..self.adjacency.len())
+            .filter(|&node| is_source_side[node])
+            .collect();
+        Cut {
+            capacity,
+            source_side,
+            edges,
+        }
+    }
+
+    fn levels(&self, source: Node, sink: Node) -> Option<Vec<Option<usize>>> {
+        let mut levels = vec![None; self.adjacency.len()];
+        levels[source] = Some(0);
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            for &edge in &self.adjacency[node] {
+                let head = self.heads[edge];
+                if levels[head].is_none() && self.residuals[edge] > Capacity::default() {
+                    levels[head] = levels[node].map(|level| level + 1);
+                    if head == sink {
+                        // Every node on a shortest path to the sink already has its level.
+                        return Some(levels);
+                    }
+                    queue.push_back(head);
+                }
+            }
+        }
+        None
+    }
+
+    fn augmenting_path(
+        &self,
+        source: Node,
+        sink: Node,
+        levels: &[Option<usize>],
+        next_edges: &mut [usize],
+    ) -> Option<Vec<usize>> {
+        let mut path: Vec<usize> = vec![];
+        let mut node = source;
+        while node != sink {
+            let advance = self.adjacency[node][next_edges[node]..]
+                .iter()
+                .position(|&edge| {
+                    let head = self.heads[edge];
+                    self.residuals[edge] > Capacity::default()
+                        && levels[head] == levels[node].map(|level| level + 1)
+                })
+                .map(|offset| next_edges[node] + offset);
+            if let Some(index) = advance {
+                next_edges[node] = index;
+                let edge = self.adjacency[node][index];
+                path.push(edge);
+                node = self.heads[edge];
+            } else {
+                next_edges[node] = self.adjacency[node].len();
+                let dead_end = path.pop()?;
+                node = self.heads[dead_end ^ 1];
+                next_edges[node] += 1;
+            }
+        }
+        Some(path)
+    }
+
+    /// Returns every node, in breadth-first order from `source` and then from the first node not
+    /// yet visited.
+    fn breadth_first_order(&self, source: Node) -> Vec<Node> {
+        let mut is_visited = vec![false; self.adjacency.len()];
+        let mut order = vec![];
+        for root in iter::once(source).chain(0..self.adjacency.len()) {
+            if is_visited[root] {
+                continue;
+            }
+            is_visited[root] = true;
+            let mut index = order.len();
+            order.push(root);
+            while let Some(&node) = order.get(index) {
+                for &edge in &self.adjacency[node] {
+                    let head = self.heads[edge];
+                    if !is_visited[head] {
+                        is_visited[head] = true;
+                        order.push(head);
+                    }
+                }
+                index += 1;
+            }
+        }
+        order
+    }
+
+    fn residual_reachable(&self, source: Node) -> Vec<bool> {
+        let mut is_reachable = vec![false; self.adjacency.len()];
+        is_reachable[source] = true;
+        let mut frontier = vec![source];
+        while let Some(node) = frontier.pop() {
+            for &edge in &self.adjacency[node] {
+                let head = self.heads[edge];
+                if !is_reachable[head] && self.residuals[edge] > Capacity::default() {
+                    is_reachable[head] = true;
+                    frontier.push(head);
+                }
+            }
+        }
+        is_reachable
+    }
+}
+
+/// Finds a global minimum cut of an undirected graph with maximum flows, or `None` if the graph has
+/// fewer than two nodes. The nodes are visited in breadth-first order from the first node, and each
+/// is merged into the nodes visited before it after computing a minimum cut between them. The first
+/// visited node on the far side of a global minimum cut is cut from the merged nodes by it, so the
+/// cheapest of these cuts is a global minimum cut. Visiting neighbors of merged nodes keeps every
+/// flow computation close to the merged nodes.
+pub fn global_minimum_cut<Capacity>(
+    number_of_nodes: usize,
+    edges: impl IntoIterator<Item = (Node, Node, Capacity)>,
+) -> Option<Cut<Capacity>>
+where
+    Capacity: Copy + Ord + Default + Add<Output = Capacity> + Sub<Output = Capacity>,
+{
+    let edges: Vec<(Node, Node, Capacity)> = edges.into_iter().collect();
+    let mut network = FlowNetwork::new(number_of_nodes);
+    let mut total_capacity = Capacity::default();
+    for &(from, to, capacity) in &edges {
+        network.add_undirected_edge(from, to, capacity);
+        total_capacity = total_capacity + capacity;
+    }
+    // No cut is as expensive as this, so merged nodes are never cut apart.
+    let unbounded = total_capacity + total_capacity;
+
+    let merged = 0;
+    let mut best: Option<(Capacity, Vec<bool>)> = None;
+    for node in network.breadth_first_order(merged).into_iter().skip(1) {
+        // The flow pushed before is a circulation once its source is merged, which keeps the
+        // residual capacity of every cut unchanged.
+        let capacity = network.max_flow(node, merged);
+        if best
+            .as_ref()
+            .map_or(true, |(best_capacity, _)| capacity < *best_capacity)
+        {
+            best = Some((capacity, network.residual_reachable(node)));
+        }
+        network.add_undirected_edge(merged, node, unbounded);
+    }
+    let (capacity, is_source_side) = best?;
+    Some(cut(edges, capacity, &is_source_side))
+}
+
+/// Finds a global minimum cut of an undirected graph with the Stoer–Wagner algorithm, or `None` if
+/// the graph has fewer than two nodes.
+// On the sparse graph of 2023 day 25, `global_minimum_cut` is about twenty times faster.
+#[allow(dead_code)]
+pub fn stoer_wagner<Weight>(
+    number_of_nodes: usize,
+    edges: impl IntoIterator<Item = (Node, Node, Weight)>,
+) -> Option<Cut<Weight>>
+where
+    Weight: Copy + Ord + Default + Add<Output = Weight>,
+{
+    let edges: Vec<(Node, Node, Weight)> = edges.into_iter().collect();
+    let mut adjacency: Vec<HashMap<Node, Weight>> = vec![HashMap::new(); number_of_nodes];
+    for &(from, to, weight) in &edges {
+        if from != to {
+            for (tail, head) in [(from, to), (to, from)] {
+                let total = adjacency[tail].entry(head).or_default();
+                *total = *total + weight;
+            }
+        }
+    }
+    let mut groups: Vec<Vec<Node>> = (0..number_of_nodes).map(|node| vec![node]).collect();
+    let mut active: Vec<Node> = (0..number_of_nodes).collect();
+    let mut best: Option<(Weight, Vec<Node>)> = None;
+
+    while active.len() > 1 {
+        let mut connectivity = vec![Weight::default(); number_of_nodes];
+        let mut is_added = vec![false; number_of_nodes];
+        let mut heap: BinaryHeap<(Weight, Node)> = active
+            .iter()
+            .map(|&node| (Weight::default(), node))
+            .collect();
+        let mut order = Vec::with_capacity(active.len());
+        while let Some((weight, node)) = heap.pop() {
+            if is_added[node] || weight != connectivity[node] {
+                continue;
+            }
+            is_added[node] = true;
+            order.push(node);
+            for (&neighbor, &weight) in &adjacency[node] {
+                if !is_added[neighbor] {
+                    connectivity[neighbor] = connectivity[neighbor] + weight;
+                    heap.push((connectivity[neighbor], neighbor));
+                }
+            }
+        }
+
+        let [second_to_last, last] = order[order.len() - 2..] else {
+            unreachable!("order should contain every active node");
+        };
+        if best
+            .as_ref()
+            .map_or(true, |(weight, _)| connectivity[last] < *weight)
+        {
+            best = Some((connectivity[last], groups[last].clone()));
+        }
+
+        let merged = std::mem::take(&mut adjacency[last]);
+        for (neighbor, weight) in merged {
+            adjacency[neighbor].remove(&last);
+            if neighbor != second_to_last {
+                for (tail, head) in [(second_to_last, neighbor), (neighbor, second_to_last)] {
+                    let total = adjacency[tail].entry(head).or_default();
+                    *total = *total + weight;
+                }
+            }
+        }
+        let group = std::mem::take(&mut groups[last]);
+        groups[second_to_last].extend(group);
+        active.retain(|&node| node != last);
+    }
+
+    let (capacity, source_side) = best?;
+    let mut is_source_side = vec![false; number_of_nodes];
+    for node in source_side {
+        is_source_side[node] = true;
+    }
+    Some(cut(edges, capacity, &is_source_side))
+}
+
+fn cut<Capacity>(
+    edges: Vec<(Node, Node, Capacity)>,
+    capacity: Capacity,
+    is_source_side: &[bool],
+) -> Cut<Capacity> {
+    let source_side = (0..is_source_side.len())
+        .filter(|&node| is_source_side[node])
+        .collect();
+    let edges = edges
+        .into_iter()
+        .filter(|&(from, to, _)| is_source_side[from] != is_source_side[to])
+        .map(|(from, to, _)| [from, to])
+        .collect();
+    Cut {
+        capacity,
+        source_side,
+        edges,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    use super::*;
+
+    type Edges = Vec<(Node, Node, usize)>;
+
+    fn random_graph(rng: &mut SmallRng) -> (usize, Edges) {
+        let number_of_nodes = rng.gen_range(2..=8);
+        let edges = (0..rng.gen_range(0..=16))
+            .map(|_| {
+                (
+                    rng.gen_range(0..number_of_nodes),
+                    rng.gen_range(0..number_of_nodes),
+                    rng.gen_range(1..=5),
+                )
+            })
+            .collect();
+        (number_of_nodes, edges)
+    }
+
+    fn cut_capacity(edges: &Edges, source_side: u32, is_directed: bool) -> usize {
+        let is_source_side = |node: Node| source_side & (1 << node) != 0;
+        edges
+            .iter()
+            .filter(|&&(from, to, _)| {
+                if is_directed {
+                    is_source_side(from) && !is_source_side(to)
+                } else {
+                    is_source_side(from) != is_source_side(to)
+                }
+            })
+            .map(|&(_, _, capacity)| capacity)
+            .sum()
+    }
+
+    #[test]
+    fn max_flow_equals_brute_force_minimum_cut() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..300 {
+            let (number_of_nodes, edges) = random_graph(&mut rng);
+            let [source, sink] = [0, number_of_nodes - 1];
+            let mut network = FlowNetwork::new(number_of_nodes);
+            for &(from, to, capacity) in &edges {
+                network.add_edge(from, to, capacity);
+            }
+            let expected = (0..1_u32 << number_of_nodes)
+                .filter(|&side| side & (1 << source) != 0 && side & (1 << sink) == 0)
+                .map(|side| cut_capacity(&edges, side, true))
+                .min();
+
+            let cut = network.minimum_cut(source, sink);
+            assert_eq!(Some(cut.capacity), expected, "flow in {edges:?}");
+            let side = cut.source_side.iter().map(|&node| 1 << node).sum();
+            assert_eq!(cut_capacity(&edges, side, true), cut.capacity);
+            let cut_edges: usize = edges
+                .iter()
+                .filter(|&&(from, to, _)| cut.edges.contains(&[from, to]))
+                .map(|&(_, _, capacity)| capacity)
+                .sum();
+            assert_eq!(cut_edges, cut.capacity, "cut edges should sum to capacity");
+        }
+    }
+
+    #[test]
+    fn max_flow_from_node_to_itself_is_zero() {
+        let mut network = FlowNetwork::new(2);
+        network.add_undirected_edge(0, 1, 3);
+        assert_eq!(network.max_flow(0, 0), 0);
+        assert_eq!(network.max_flow(0, 1), 3);
+    }
+
+    fn assert_global_minimum_cut(
+        rng: &mut SmallRng,
+        global_minimum_cut: fn(usize, Edges) -> Option<Cut<usize>>,
+    ) {
+        for _ in 0..300 {
+            let (number_of_nodes, edges) = random_graph(rng);
+            let expected = (1..(1_u32 << number_of_nodes) - 1)
+                .map(|side| cut_capacity(&edges, side, false))
+                .min();
+
+            let cut = global_minimum_cut(number_of_nodes, edges.clone())
+                .expect("graph should have at least two nodes");
+            assert_eq!(Some(cut.capacity), expected, "cut in {edges:?}");
+            assert!(
+                !cut.source_side.is_empty() && cut.source_side.len() < number_of_nodes,
+                "cut should separate the graph into two non-empty parts"
+            );
+            let side = cut.source_side.iter().map(|&node| 1 << node).sum();
+            assert_eq!(cut_capacity(&edges, side, false), cut.capacity);
+        }
+    }
+
+    #[test]
+    fn merged_flows_equal_brute_force_global_minimum_cut() {
+        assert_global_minimum_cut(&mut SmallRng::seed_from_u64(2), |number_of_nodes, edges| {
+            global_minimum_cut(number_of_nodes, edges)
+        });
+    }
+
+    #[test]
+    fn stoer_wagner_equals_brute_force_global_minimum_cut() {
+        assert_global_minimum_cut(&mut SmallRng::seed_from_u64(1), |number_of_nodes, edges| {
+            stoer_wagner(number_of_nodes, edges)
+        });
+    }
+
+    #[test]
+    fn global_minimum_cuts_need_two_nodes() {
+        assert_eq!(global_minimum_cut::<usize>(1, []), None);
+        assert_eq!(stoer_wagner::<usize>(1, []), None);
+    }
+}
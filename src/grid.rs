@@ -18,6 +18,13 @@ pub struct Grid<T> {
     width: usize,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Components {
+    pub labels: Grid<usize>,
+    pub sizes: Vec<usize>,
+    pub bounding_boxes: Vec<[Position; 2]>,
+}
+
 impl<T> Grid<T> {
     pub fn new(height: usize, width: usize, mut element: impl FnMut(Position) -> T) -> Self {
         let mut elements = vec![];
@@ -51,6 +58,8 @@ impl<T> Grid<T> {
         })
     }
 
+    // 2023 day 14 tilts its platform on a `BitGrid` now, which left this without a caller.
+    #[allow(dead_code)]
     pub fn iter_column_major(&self) -> impl Iterator<Item = (Position, &T)> {
         self.columns()
             .enumerate()
@@ -87,6 +96,68 @@ impl<T> Grid<T> {
         }
     }
 
+    pub fn flood_fill(
+        &self,
+        seeds: impl IntoIterator<Item = Position>,
+        mut is_passable: impl FnMut(Position, &T) -> bool,
+    ) -> Grid<bool> {
+        let mut filled = self.map(|_, _| false);
+        let mut frontier = seeds.into_iter().collect_vec();
+        while let Some(position) = frontier.pop() {
+            let Some(element) = self.get(position) else {
+                continue;
+            };
+            if filled[position] || !is_passable(position, element) {
+                continue;
+            }
+            filled[position] = true;
+            frontier.extend(neighbors(position));
+        }
+        filled
+    }
+
+    // No puzzle labels components yet; `flood_fill` covers the single-region cases.
+    #[allow(dead_code)]
+    pub fn connected_components(&self, mut is_connected: impl FnMut(&T, &T) -> bool) -> Components {
+        let mut labels: Grid<Option<usize>> = self.map(|_, _| None);
+        let mut sizes = vec![];
+        let mut bounding_boxes = vec![];
+        for (seed, _) in self.iter_row_major() {
+            if labels[seed].is_some() {
+                continue;
+            }
+            let label = sizes.len();
+            labels[seed] = Some(label);
+            let (mut size, mut bounding_box) = (0, [seed, seed]);
+            let mut frontier = vec![seed];
+            while let Some(position) = frontier.pop() {
+                size += 1;
+                bounding_box = [
+                    [0, 1].map(|index| bounding_box[0][index].min(position[index])),
+                    [0, 1].map(|index| bounding_box[1][index].max(position[index])),
+                ];
+                for neighbor in neighbors(position) {
+                    let Some(neighbor_element) = self.get(neighbor) else {
+                        continue;
+                    };
+                    if labels[neighbor].is_none() && is_connected(&self[position], neighbor_element)
+                    {
+                        labels[neighbor] = Some(label);
+                        frontier.push(neighbor);
+                    }
+                }
+            }
+            sizes.push(size);
+            bounding_boxes.push(bounding_box);
+        }
+        let labels = labels.map(|_, label| label.expect("every position should be labelled"));
+        Components {
+            labels,
+            sizes,
+            bounding_boxes,
+        }
+    }
+
     pub fn corners_clockwise(&self) -> [Position; 4] {
         let corners = [
             [0, 0],
@@ -204,3 +275,68 @@ pub const DIRECTIONS: [Direction; 4] = [NORTH, EAST, SOUTH, WEST];
 pub fn neighbors(position: Position) -> [Position; 4] {
     DIRECTIONS.map(|direction| position.add(direction))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRID: &str = "\
+        ..#..\n\
+        .##..\n\
+        #..#.\n\
+        ...#.\n\
+    ";
+
+    #[test]
+    fn flood_fill() {
+        let grid = Grid::<char>::from(GRID);
+        let filled = grid.flood_fill([[0, 0], [3, 4]], |_, &element| element == '.');
+        let expected = Grid::<char>::from(
+            "\
+            xx.xx\n\
+            x..xx\n\
+            ....x\n\
+            ....x\n\
+        ",
+        )
+        .map(|_, &element| element == 'x');
+        assert_eq!(filled, expected);
+    }
+
+    #[test]
+    fn flood_fill_from_impassable_seed() {
+        let grid = Grid::<char>::from(GRID);
+        let filled = grid.flood_fill([[0, 2], [-1, 0]], |_, &element| element == '.');
+        assert!(
+            filled.iter_row_major().all(|(_, &is_filled)| !is_filled),
+            "nothing should be filled from impassable or outside seeds"
+        );
+    }
+
+    #[test]
+    fn connected_components() {
+        let grid = Grid::<char>::from(GRID);
+        let components = grid.connected_components(|left, right| left == right);
+        let expected_labels = Grid::<usize>::from(
+            "\
+            00122\n\
+            01122\n\
+            34452\n\
+            44452\n\
+        ",
+        );
+        assert_eq!(components.labels, expected_labels);
+        assert_eq!(components.sizes, vec![3, 3, 6, 1, 5, 2]);
+        assert_eq!(
+            components.bounding_boxes,
+            vec![
+                [[0, 0], [1, 1]],
+                [[0, 1], [1, 2]],
+                [[0, 3], [3, 4]],
+                [[2, 0], [2, 0]],
+                [[2, 0], [3, 2]],
+                [[2, 3], [3, 3]],
+            ]
+        );
+    }
+}
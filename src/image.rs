@@ -0,0 +1,399 @@
+use std::{fs, path::Path};
+
+use anyhow::{anyhow, Result};
+use easy_cast::Cast;
+use itertools::Itertools;
+
+use crate::{
+    grid::{Grid, Position},
+    vector::Vector,
+};
+
+pub type Color = [u8; 3];
+
+pub const BLACK: Color = [0x00, 0x00, 0x00];
+pub const DARK_GRAY: Color = [0x40, 0x40, 0x40];
+pub const GRAY: Color = [0x80, 0x80, 0x80];
+pub const WHITE: Color = [0xff, 0xff, 0xff];
+pub const RED: Color = [0xe0, 0x30, 0x30];
+pub const BLUE: Color = [0x30, 0x60, 0xe0];
+pub const YELLOW: Color = [0xf0, 0xd0, 0x30];
+
+const CELL_SIZE: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    cells: Grid<Color>,
+    paths: Vec<(Vec<Position>, Color)>,
+    highlights: Vec<(Position, Color)>,
+}
+
+impl Image {
+    pub fn new<T>(grid: &Grid<T>, mut color: impl FnMut(Position, &T) -> Color) -> Self {
+        Self {
+            cells: grid.map(|position, element| color(position, element)),
+            paths: vec![],
+            highlights: vec![],
+        }
+    }
+
+    pub fn add_path(&mut self, path: impl IntoIterator<Item = Position>, color: Color) {
+        self.paths.push((path.into_iter().collect_vec(), color));
+    }
+
+    pub fn highlight(&mut self, cells: impl IntoIterator<Item = Position>, color: Color) {
+        self.highlights
+            .extend(cells.into_iter().map(|position| (position, color)));
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let bytes = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("ppm") => self.ppm(),
+            Some("png") => self.png(),
+            Some("svg") => self.svg().into_bytes(),
+            _ => {
+                return Err(anyhow!(
+                    "image file extension should be 'ppm', 'png' or 'svg'"
+                ))
+            }
+        };
+        fs::write(path, bytes)?;
+        Ok(())
+    }
+
+    pub fn ppm(&self) -> Vec<u8> {
+        let pixels = self.pixels();
+        let mut ppm = format!("P6\n{} {}\n255\n", pixels.width(), pixels.height()).into_bytes();
+        ppm.extend(pixels.iter_row_major().flat_map(|(_, &color)| color));
+        ppm
+    }
+
+    pub fn png(&self) -> Vec<u8> {
+        let pixels = self.pixels();
+        let mut png = PNG_SIGNATURE.to_vec();
+        png_chunk(&mut png, *b"IHDR", &png_header(&pixels));
+        png_chunk(&mut png, *b"IDAT", &zlib(&png_scanlines(&pixels)));
+        png_chunk(&mut png, *b"IEND", &[]);
+        png
+    }
+
+    pub fn animated_png(frames: &[Self], frames_per_second: u16) -> Vec<u8> {
+        let frames = frames.iter().map(Self::pixels).collect_vec();
+        let first_frame = frames
+            .first()
+            .expect("animation should have at least one frame");
+        let dimensions = [first_frame.height(), first_frame.width()];
+        assert!(
+            frames
+                .iter()
+                .all(|frame| [frame.height(), frame.width()] == dimensions),
+            "every frame should have the same dimensions"
+        );
+
+        let mut png = PNG_SIGNATURE.to_vec();
+        png_chunk(&mut png, *b"IHDR", &png_header(first_frame));
+        let number_of_frames: u32 = frames.len().cast();
+        let animation_control = [number_of_frames.to_be_bytes(), 0_u32.to_be_bytes()].concat();
+        png_chunk(&mut png, *b"acTL", &animation_control);
+        let mut sequence_number: u32 = 0;
+        for (index, frame) in frames.iter().enumerate() {
+            let mut frame_control = sequence_number.to_be_bytes().to_vec();
+            frame_control.extend(&png_header(frame)[..8]);
+            frame_control.extend([0; 8]);
+            frame_control.extend(1_u16.to_be_bytes());
+            frame_control.extend(frames_per_second.to_be_bytes());
+            frame_control.extend([0, 0]);
+            png_chunk(&mut png, *b"fcTL", &frame_control);
+            sequence_number += 1;
+
+            let data = zlib(&png_scanlines(frame));
+            if index == 0 {
+                png_chunk(&mut png, *b"IDAT", &data);
+            } else {
+                let frame_data = [&sequence_number.to_be_bytes()[..], &data].concat();
+                png_chunk(&mut png, *b"fdAT", &frame_data);
+                sequence_number += 1;
+            }
+        }
+        png_chunk(&mut png, *b"IEND", &[]);
+        png
+    }
+
+    pub fn svg(&self) -> String {
+        let [height, width] = [self.cells.height(), self.cells.width()];
+        let mut elements = vec![format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+            viewBox=\"0 0 {width} {height}\" shape-rendering=\"crispEdges\">",
+            width * CELL_SIZE,
+            height * CELL_SIZE,
+        )];
+        for (row_index, row) in self.cells.rows().enumerate() {
+            let mut column_index = 0;
+            for (run_length, color) in row.dedup_with_count() {
+                elements.push(format!(
+                    "<rect x=\"{column_index}\" y=\"{row_index}\" width=\"{run_length}\" \
+                    height=\"1\" fill=\"{}\"/>",
+                    hex(*color),
+                ));
+                column_index += run_length;
+            }
+        }
+        for (path, color) in &self.paths {
+            let points = path
+                .iter()
+                .map(|&[row, column]| format!("{column}.5,{row}.5"))
+                .join(" ");
+            elements.push(format!(
+                "<polyline points=\"{points}\" fill=\"none\" stroke=\"{}\" \
+                stroke-width=\"0.2\"/>",
+                hex(*color),
+            ));
+        }
+        for &([row, column], color) in &self.highlights {
+            elements.push(format!(
+                "<rect x=\"{column}\" y=\"{row}\" width=\"1\" height=\"1\" fill=\"none\" \
+                stroke=\"{}\" stroke-width=\"0.2\"/>",
+                hex(color),
+            ));
+        }
+        elements.push("</svg>\n".to_owned());
+        elements.join("\n")
+    }
+
+    fn pixels(&self) -> Grid<Color> {
+        let cell_size: isize = CELL_SIZE.cast();
+        let [height, width] = [self.cells.height(), self.cells.width()];
+        let mut pixels = Grid::new(height * CELL_SIZE, width * CELL_SIZE, |[row, column]| {
+            self.cells[[row / cell_size, column / cell_size]]
+        });
+        for (path, color) in &self.paths {
+            for (&from, &to) in path.iter().tuple_windows() {
+                draw_line(&mut pixels, cell_center(from), cell_center(to), *color);
+            }
+        }
+        for &([row, column], color) in &self.highlights {
+            let [top, left] = [row * cell_size, column * cell_size];
+            let [bottom, right] = [top + cell_size - 1, left + cell_size - 1];
+            for [from, to] in [
+                [[top, left], [top, right]],
+                [[top, right], [bottom, right]],
+                [[bottom, right], [bottom, left]],
+                [[bottom, left], [top, left]],
+            ] {
+                draw_line(&mut pixels, from, to, color);
+            }
+        }
+        pixels
+    }
+}
+
+fn cell_center([row, column]: Position) -> Position {
+    let cell_size: isize = CELL_SIZE.cast();
+    [
+        row * cell_size + cell_size / 2,
+        column * cell_size + cell_size / 2,
+    ]
+}
+
+fn draw_line(pixels: &mut Grid<Color>, from: Position, to: Position, color: Color) {
+    let difference = to.sub(from);
+    let [row_distance, column_distance] = difference.abs();
+    let [row_step, column_step] = difference.signum();
+    let mut error = column_distance - row_distance;
+    let mut position = from;
+    loop {
+        if let Some(pixel) = pixels.get_mut(position) {
+            *pixel = color;
+        }
+        if position == to {
+            return;
+        }
+        let doubled_error = 2 * error;
+        if doubled_error >= -row_distance {
+            error -= row_distance;
+            position[1] += column_step;
+        }
+        if doubled_error <= column_distance {
+            error += column_distance;
+            position[0] += row_step;
+        }
+    }
+}
+
+fn hex([red, green, blue]: Color) -> String {
+    format!("#{red:02x}{green:02x}{blue:02x}")
+}
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+fn png_header(pixels: &Grid<Color>) -> Vec<u8> {
+    let [height, width]: [u32; 2] = [pixels.height().cast(), pixels.width().cast()];
+    let mut header = vec![];
+    header.extend(width.to_be_bytes());
+    header.extend(height.to_be_bytes());
+    header.extend([8, 2, 0, 0, 0]);
+    header
+}
+
+fn png_scanlines(pixels: &Grid<Color>) -> Vec<u8> {
+    let mut scanlines = vec![];
+    for row in pixels.rows() {
+        scanlines.push(0);
+        scanlines.extend(row.flatten());
+    }
+    scanlines
+}
+
+fn png_chunk(png: &mut Vec<u8>, kind: [u8; 4], data: &[u8]) {
+    let length: u32 = data.len().cast();
+    png.extend(length.to_be_bytes());
+    png.extend(kind);
+    png.extend(data);
+    png.extend(crc32(kind.iter().chain(data)).to_be_bytes());
+}
+
+fn zlib(data: &[u8]) -> Vec<u8> {
+    const MAX_STORED_BLOCK_LENGTH: usize = 0xffff;
+
+    let mut zlib = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_STORED_BLOCK_LENGTH).peekable();
+    if blocks.peek().is_none() {
+        zlib.extend([0x01, 0x00, 0x00, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let is_final = blocks.peek().is_none();
+        let length: u16 = block.len().cast();
+        zlib.push(u8::from(is_final));
+        zlib.extend(length.to_le_bytes());
+        zlib.extend((!length).to_le_bytes());
+        zlib.extend(block);
+    }
+    zlib.extend(adler32(data).to_be_bytes());
+    zlib
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MODULUS: u32 = 65521;
+
+    let [mut a, mut b] = [1, 0];
+    for &byte in data {
+        a = (a + u32::from(byte)) % MODULUS;
+        b = (b + a) % MODULUS;
+    }
+    (b << 16) | a
+}
+
+fn crc32<'bytes>(bytes: impl IntoIterator<Item = &'bytes u8>) -> u32 {
+    const fn crc32_table() -> [u32; 256] {
+        let mut table = [0; 256];
+        let mut index: u32 = 0;
+        while index < 256 {
+            let mut crc = index;
+            let mut bit = 0;
+            while bit < 8 {
+                crc = if crc & 1 == 1 {
+                    0xedb8_8320 ^ (crc >> 1)
+                } else {
+                    crc >> 1
+                };
+                bit += 1;
+            }
+            table[index as usize] = crc;
+            index += 1;
+        }
+        table
+    }
+
+    const CRC32_TABLE: [u32; 256] = crc32_table();
+    let crc = bytes.into_iter().fold(0xffff_ffff, |crc: u32, &byte| {
+        CRC32_TABLE[usize::from(crc.to_le_bytes()[0] ^ byte)] ^ (crc >> 8)
+    });
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image() -> Image {
+        let grid = Grid::<char>::from("#.\n.#\n");
+        let mut image = Image::new(
+            &grid,
+            |_, &element| if element == '#' { WHITE } else { BLACK },
+        );
+        image.add_path([[0, 0], [1, 1]], RED);
+        image.highlight([[0, 1]], YELLOW);
+        image
+    }
+
+    #[test]
+    fn ppm() {
+        let ppm = image().ppm();
+        let header = b"P6\n10 10\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        assert_eq!(ppm.len(), header.len() + 10 * 10 * 3);
+        let pixel = |row: usize, column: usize| {
+            let index = header.len() + (row * 10 + column) * 3;
+            [ppm[index], ppm[index + 1], ppm[index + 2]]
+        };
+        assert_eq!(pixel(0, 0), WHITE, "top left cell should have its color");
+        assert_eq!(pixel(7, 2), BLACK, "bottom left cell should have its color");
+        assert_eq!(
+            pixel(4, 4),
+            RED,
+            "path should be drawn between cell centers"
+        );
+        assert_eq!(pixel(0, 5), YELLOW, "highlighted cell should have a border");
+    }
+
+    #[test]
+    fn steep_and_shallow_paths() {
+        let grid = Grid::<char>::from("...\n...\n...\n");
+        for (to, center) in [([1, 2], [7, 12]), ([2, 1], [12, 7])] {
+            let mut image = Image::new(&grid, |_, _| BLACK);
+            image.add_path([[0, 0], to], RED);
+            let pixels = image.pixels();
+            let [rows, columns] = [2..=center[0], 2..=center[1]];
+            assert!(
+                rows.clone()
+                    .all(|row| columns.clone().any(|column| pixels[[row, column]] == RED))
+                    && columns
+                        .clone()
+                        .all(|column| rows.clone().any(|row| pixels[[row, column]] == RED)),
+                "path to {to:?} should cover every row and column it spans"
+            );
+            assert_eq!(pixels[center], RED, "path should end at a cell center");
+        }
+    }
+
+    #[test]
+    fn png_checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn png_structure() {
+        let png = image().png();
+        assert_eq!(&png[..8], &PNG_SIGNATURE);
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..24], &[0, 0, 0, 10, 0, 0, 0, 10]);
+        assert_eq!(&png[png.len() - 12..], b"\0\0\0\0IEND\xae\x42\x60\x82");
+    }
+
+    #[test]
+    fn svg() {
+        let svg = image().svg();
+        let expected = [
+            "<rect x=\"0\" y=\"0\" width=\"1\" height=\"1\" fill=\"#ffffff\"/>",
+            "<polyline points=\"0.5,0.5 1.5,1.5\" fill=\"none\" stroke=\"#e03030\" \
+            stroke-width=\"0.2\"/>",
+            "<rect x=\"1\" y=\"0\" width=\"1\" height=\"1\" fill=\"none\" stroke=\"#f0d030\" \
+            stroke-width=\"0.2\"/>",
+        ];
+        for element in expected {
+            assert!(svg.contains(element), "svg should contain '{element}'");
+        }
+    }
+}
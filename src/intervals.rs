@@ -0,0 +1,333 @@
+use std::{
+    cmp,
+    iter::Sum,
+    ops::{Add, Range, Sub},
+};
+
+use itertools::Itertools;
+
+/// A set of values stored as sorted, disjoint, non-adjacent and non-empty half-open intervals.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Range<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { intervals: vec![] }
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn intervals(&self) -> &[Range<T>] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let index = self
+            .intervals
+            .partition_point(|interval| interval.end <= *value);
+        self.intervals
+            .get(index)
+            .is_some_and(|interval| interval.contains(value))
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    // 2022 day 15 collects its covered ranges with `FromIterator`, which merges them already.
+    #[allow(dead_code)]
+    pub fn union(&self, other: &Self) -> Self {
+        self.intervals
+            .iter()
+            .chain(&other.intervals)
+            .cloned()
+            .collect()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+        let [mut left, mut right] = [
+            self.intervals.iter().peekable(),
+            other.intervals.iter().peekable(),
+        ];
+        while let (Some(left_interval), Some(right_interval)) = (left.peek(), right.peek()) {
+            let start = cmp::max(left_interval.start, right_interval.start);
+            let end = cmp::min(left_interval.end, right_interval.end);
+            if start < end {
+                intervals.push(start..end);
+            }
+            if left_interval.end < right_interval.end {
+                left.next();
+            } else {
+                right.next();
+            }
+        }
+        Self { intervals }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+        let mut others = other.intervals.iter().peekable();
+        for interval in &self.intervals {
+            let mut start = interval.start;
+            while let Some(other) = others.next_if(|other| other.end <= interval.end) {
+                if start < other.start {
+                    intervals.push(start..other.start);
+                }
+                start = cmp::max(start, other.end);
+            }
+            let end = others.peek().map_or(interval.end, |other| {
+                cmp::max(start, cmp::min(interval.end, other.start))
+            });
+            if start < end {
+                intervals.push(start..end);
+            }
+        }
+        Self { intervals }
+    }
+
+    /// Returns the values within `universe` that are not in the set.
+    // 2022 day 15 finds its distress beacon with `manhattan::Coverage` instead of per row.
+    #[allow(dead_code)]
+    pub fn complement(&self, universe: Range<T>) -> Self {
+        Self::from(universe).difference(self)
+    }
+
+    pub fn total_length(&self) -> T
+    where
+        T: Sub<Output = T> + Sum,
+    {
+        self.intervals
+            .iter()
+            .map(|interval| interval.end - interval.start)
+            .sum()
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for IntervalSet<T> {
+    fn from(interval: Range<T>) -> Self {
+        Self::from_iter([interval])
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(intervals: I) -> Self {
+        let mut sorted = intervals
+            .into_iter()
+            .filter(|interval| interval.start < interval.end)
+            .sorted_unstable_by_key(|interval| interval.start);
+        let mut intervals: Vec<Range<T>> = sorted.next().into_iter().collect();
+        for interval in sorted {
+            let last = intervals
+                .last_mut()
+                .expect("at least one interval should exist");
+            if interval.start <= last.end {
+                last.end = cmp::max(last.end, interval.end);
+            } else {
+                intervals.push(interval);
+            }
+        }
+        Self { intervals }
+    }
+}
+
+/// A function that adds a constant offset to every value within each of a number of disjoint
+/// half-open intervals, and leaves all other values unchanged.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalMap<T> {
+    pieces: Vec<(Range<T>, T)>,
+}
+
+impl<T> Default for IntervalMap<T> {
+    fn default() -> Self {
+        Self { pieces: vec![] }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> IntervalMap<T> {
+    // 2023 day 5 maps whole ranges of seeds through `image`, never a single value.
+    #[allow(dead_code)]
+    pub fn get(&self, value: T) -> T {
+        let index = self
+            .pieces
+            .partition_point(|(interval, _)| interval.end <= value);
+        match self.pieces.get(index) {
+            Some((interval, offset)) if interval.contains(&value) => value + *offset,
+            _ => value,
+        }
+    }
+
+    pub fn image(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        let domain = self.domain();
+        let unmapped = set.difference(&domain);
+        let mapped = self.pieces.iter().flat_map(|(interval, offset)| {
+            let intersection = set.intersection(&IntervalSet::from(interval.clone()));
+            intersection
+                .intervals
+                .into_iter()
+                .map(|interval| interval.start + *offset..interval.end + *offset)
+        });
+        unmapped.intervals.into_iter().chain(mapped).collect()
+    }
+
+    /// Returns the map that applies `self` followed by `next`.
+    pub fn then(&self, next: &Self) -> Self {
+        let mut pieces = vec![];
+        for (interval, offset) in &self.pieces {
+            let image = interval.start + *offset..interval.end + *offset;
+            let mut untouched = IntervalSet::from(image.clone());
+            for (next_interval, next_offset) in &next.pieces {
+                let start = cmp::max(image.start, next_interval.start);
+                let end = cmp::min(image.end, next_interval.end);
+                if start < end {
+                    pieces.push((start - *offset..end - *offset, *offset + *next_offset));
+                    untouched = untouched.difference(&IntervalSet::from(start..end));
+                }
+            }
+            for interval in untouched.intervals {
+                pieces.push((interval.start - *offset..interval.end - *offset, *offset));
+            }
+        }
+        let domain = self.domain();
+        for (next_interval, next_offset) in &next.pieces {
+            let outside = IntervalSet::from(next_interval.clone()).difference(&domain);
+            pieces.extend(
+                outside
+                    .intervals
+                    .into_iter()
+                    .map(|interval| (interval, *next_offset)),
+            );
+        }
+        pieces.into_iter().collect()
+    }
+
+    fn domain(&self) -> IntervalSet<T> {
+        self.pieces
+            .iter()
+            .map(|(interval, _)| interval.clone())
+            .collect()
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<(Range<T>, T)> for IntervalMap<T> {
+    fn from_iter<I: IntoIterator<Item = (Range<T>, T)>>(pieces: I) -> Self {
+        let pieces = pieces
+            .into_iter()
+            .filter(|(interval, _)| interval.start < interval.end)
+            .sorted_unstable_by_key(|(interval, _)| interval.start)
+            .collect_vec();
+        assert!(
+            pieces
+                .windows(2)
+                .all(|pair| pair[0].0.end <= pair[1].0.start),
+            "intervals of a map should be disjoint"
+        );
+        Self { pieces }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    use super::*;
+    use crate::HashSet;
+
+    fn random_set(rng: &mut SmallRng) -> IntervalSet<i32> {
+        (0..rng.gen_range(0..5))
+            .map(|_| {
+                let start = rng.gen_range(-20..20);
+                start..start + rng.gen_range(0..10)
+            })
+            .collect()
+    }
+
+    fn values(set: &IntervalSet<i32>) -> HashSet<i32> {
+        set.intervals().iter().cloned().flatten().collect()
+    }
+
+    #[test]
+    fn normalization() {
+        let set = IntervalSet::from_iter([5..7, 0..2, 1..3, 3..4, 8..8]);
+        assert_eq!(set.intervals(), [0..4, 5..7]);
+        assert_eq!(set.total_length(), 6);
+        assert!(set.contains(&3));
+        assert!(!set.contains(&4));
+    }
+
+    #[test]
+    fn complement() {
+        let set = IntervalSet::from_iter([-3..14, 15..26]);
+        assert_eq!(set.complement(0..21), IntervalSet::from(14..15));
+        let set = IntervalSet::from(7..9);
+        assert_eq!(set.complement(0..21).intervals(), [0..7, 9..21]);
+        let set = IntervalSet::from(-10..0);
+        assert_eq!(set.complement(0..21), IntervalSet::from(0..21));
+    }
+
+    #[test]
+    fn set_operations_match_sets_of_values() {
+        let mut rng = SmallRng::seed_from_u64(39);
+        for _ in 0..1000 {
+            let [left, right] = [random_set(&mut rng), random_set(&mut rng)];
+            let [left_values, right_values] = [values(&left), values(&right)];
+            let union = left_values.union(&right_values).copied().collect();
+            let intersection = left_values.intersection(&right_values).copied().collect();
+            let difference = left_values.difference(&right_values).copied().collect();
+            let union_values = values(&left.union(&right));
+            assert_eq!(union_values, union, "union should match");
+            assert_eq!(
+                values(&left.intersection(&right)),
+                intersection,
+                "intersection should match"
+            );
+            assert_eq!(
+                values(&left.difference(&right)),
+                difference,
+                "difference should match"
+            );
+            assert_eq!(
+                left.union(&right),
+                union_values
+                    .into_iter()
+                    .map(|value| value..value + 1)
+                    .collect(),
+                "union should be normalized"
+            );
+        }
+    }
+
+    #[test]
+    fn composition_matches_applying_maps_in_turn() {
+        let mut rng = SmallRng::seed_from_u64(5);
+        for _ in 0..1000 {
+            let [first, second] = [0, 1].map(|_| {
+                random_set(&mut rng)
+                    .intervals()
+                    .iter()
+                    .map(|interval| (interval.clone(), rng.gen_range(-10..10)))
+                    .collect::<IntervalMap<i32>>()
+            });
+            let composition = first.then(&second);
+            let set = random_set(&mut rng);
+            for value in -40..40 {
+                assert_eq!(
+                    composition.get(value),
+                    second.get(first.get(value)),
+                    "value should map equally"
+                );
+            }
+            let image: HashSet<i32> = values(&set)
+                .into_iter()
+                .map(|value| first.get(value))
+                .collect();
+            assert_eq!(values(&first.image(&set)), image, "image should match");
+        }
+    }
+}
@@ -1,139 +1,86 @@
-use itertools::Itertools;
+pub mod matrix;
 
-use crate::floating_point::ApproxEq;
+use std::ops::{Div, Neg, Sub};
 
-type Matrix<const NUM_ROWS: usize, const NUM_COLUMNS: usize> = [[Real; NUM_COLUMNS]; NUM_ROWS];
-type SolutionSet = Option<(Origin, Vec<Direction>)>;
-type Origin = Vec<Real>;
-type Direction = Vec<Real>;
-type Real = f64;
+use num_traits::{One, PrimInt, Signed, Zero};
 
-#[must_use]
-pub fn solution_set<const NUM_ROWS: usize, const NUM_COLUMNS: usize>(
-    augmented_matrix: Matrix<NUM_ROWS, NUM_COLUMNS>,
-) -> SolutionSet {
-    let reduced_row_echelon_form = reduced_row_echelon_form(augmented_matrix);
-    solution_set_from_reduced_row_echelon_form(reduced_row_echelon_form)
-}
+pub use self::matrix::Matrix;
+use crate::{floating_point::ApproxEq, rational::Rational};
 
-fn reduced_row_echelon_form<const NUM_ROWS: usize, const NUM_COLUMNS: usize>(
-    mut matrix: Matrix<NUM_ROWS, NUM_COLUMNS>,
-) -> Matrix<NUM_ROWS, NUM_COLUMNS> {
-    let [mut pivot_row, mut pivot_column] = [0, 0];
-    while pivot_row < NUM_ROWS && pivot_column < NUM_COLUMNS {
-        let row_with_max_abs_value = row_with_max_abs_value(matrix, pivot_row, pivot_column);
-        let pivot = matrix[row_with_max_abs_value][pivot_column];
-        if pivot.approx_eq(0.0) {
-            pivot_column += 1;
-            continue;
-        }
+type SolutionSet<F> = Option<(Origin<F>, Vec<Direction<F>>)>;
+type Origin<F> = Vec<F>;
+type Direction<F> = Vec<F>;
 
-        [matrix[row_with_max_abs_value], matrix[pivot_row]] =
-            [matrix[pivot_row], matrix[row_with_max_abs_value]];
-        subtract_pivot_row_from_other_rows(&mut matrix, pivot_row, pivot_column, pivot);
-        divide_pivot_row_by_pivot(&mut matrix, pivot_row, pivot_column, pivot);
-        [pivot_row, pivot_column] = [pivot_row + 1, pivot_column + 1];
-    }
-    matrix
-}
+/// The scalars of a system of linear equations.
+pub trait Field:
+    Copy + Zero + One + Sub<Output = Self> + Div<Output = Self> + Neg<Output = Self>
+{
+    /// Returns whether the value should be treated as zero when looking for a pivot.
+    fn is_negligible(self) -> bool;
 
-fn row_with_max_abs_value<const NUM_ROWS: usize, const NUM_COLUMNS: usize>(
-    matrix: Matrix<NUM_ROWS, NUM_COLUMNS>,
-    first_row: usize,
-    column: usize,
-) -> usize {
-    let row_with_max_abs_value = matrix[first_row..]
-        .iter()
-        .map(|row| row[column].abs())
-        .position_max_by(f64::total_cmp)
-        .expect("first row should be within maxtrix");
-    row_with_max_abs_value + first_row
+    /// Returns how suitable the value is as a pivot, where larger is better.
+    fn pivot_preference(self) -> f64;
 }
 
-fn subtract_pivot_row_from_other_rows<const NUM_ROWS: usize, const NUM_COLUMNS: usize>(
-    matrix: &mut Matrix<NUM_ROWS, NUM_COLUMNS>,
-    pivot_row: usize,
-    pivot_column: usize,
-    pivot: f64,
-) {
-    for row in (0..NUM_ROWS).filter(|&row| row != pivot_row) {
-        let ratio = matrix[row][pivot_column] / pivot;
-        matrix[row][pivot_column] = 0.0;
-        for column in pivot_column + 1..NUM_COLUMNS {
-            matrix[row][column] -= matrix[pivot_row][column] * ratio;
-        }
+impl Field for f64 {
+    fn is_negligible(self) -> bool {
+        self.approx_eq(0.0)
     }
-}
 
-fn divide_pivot_row_by_pivot<const NUM_ROWS: usize, const NUM_COLUMNS: usize>(
-    matrix: &mut Matrix<NUM_ROWS, NUM_COLUMNS>,
-    pivot_row: usize,
-    pivot_column: usize,
-    pivot: f64,
-) {
-    for column in pivot_column..NUM_COLUMNS {
-        matrix[pivot_row][column] /= pivot;
+    /// Prefers large values, which keeps rounding errors small.
+    fn pivot_preference(self) -> f64 {
+        self.abs()
     }
 }
 
-fn solution_set_from_reduced_row_echelon_form<const NUM_ROWS: usize, const NUM_COLUMNS: usize>(
-    matrix: Matrix<NUM_ROWS, NUM_COLUMNS>,
-) -> SolutionSet {
-    let [mut pivot_row, mut pivot_column] = [0, 0];
-    let mut free_parameters = vec![];
-    let mut origin = vec![];
-    let mut directions = vec![];
-    while pivot_row < NUM_ROWS && pivot_column < NUM_COLUMNS - 1 {
-        if matrix[pivot_row][pivot_column].approx_eq(0.0) {
-            origin.push(0.0);
-            directions.push(solution_set_direction(
-                matrix,
-                pivot_row,
-                pivot_column,
-                &free_parameters,
-            ));
-            free_parameters.push(pivot_column);
-            pivot_column += 1;
-        } else {
-            origin.push(matrix[pivot_row][NUM_COLUMNS - 1]);
-            [pivot_row, pivot_column] = [pivot_row + 1, pivot_column + 1];
-        }
-    }
-    if pivot_row < NUM_ROWS && matrix[pivot_row][NUM_COLUMNS - 1].approx_eq(1.0) {
-        None
-    } else {
-        Some((origin, directions))
+impl<T: PrimInt + Signed> Field for Rational<T> {
+    fn is_negligible(self) -> bool {
+        self.is_zero()
     }
-}
 
-fn solution_set_direction<const NUM_ROWS: usize, const NUM_COLUMNS: usize>(
-    matrix: Matrix<NUM_ROWS, NUM_COLUMNS>,
-    pivot_row: usize,
-    pivot_column: usize,
-    free_parameters: &[usize],
-) -> Vec<f64> {
-    let mut direction = negated_column(&matrix[..pivot_row], pivot_column);
-    for &parameter in free_parameters {
-        direction.insert(parameter, 0.0);
+    /// Prefers values with short numerators and denominators, which keeps the intermediate values
+    /// of exact elimination from overflowing.
+    fn pivot_preference(self) -> f64 {
+        if self.is_zero() {
+            return f64::NEG_INFINITY;
+        }
+        let bits = |value: T| T::zero().count_zeros() - value.abs().leading_zeros();
+        -f64::from(bits(self.numerator()) + bits(self.denominator()))
     }
-    direction.push(1.0);
-    direction.resize(NUM_COLUMNS - 1, 0.0);
-    direction
 }
 
-fn negated_column<const NUM_COLUMNS: usize>(
-    matrix: &[[Real; NUM_COLUMNS]],
-    column: usize,
-) -> Vec<Real> {
-    matrix.iter().map(|row| -row[column]).collect_vec()
+/// Returns the solutions of the system of linear equations whose augmented matrix is given, as an
+/// origin and a basis of directions in which the solutions extend, or `None` if there are none.
+#[must_use]
+pub fn solution_set<F: Field>(augmented_matrix: impl Into<Matrix<F>>) -> SolutionSet<F> {
+    let augmented_matrix = augmented_matrix.into();
+    let number_of_unknowns = augmented_matrix.number_of_columns() - 1;
+    let (reduced_row_echelon_form, pivot_columns) = augmented_matrix.reduced_row_echelon_form();
+    if pivot_columns.last() == Some(&number_of_unknowns) {
+        return None;
+    }
+    let mut origin = vec![F::zero(); number_of_unknowns];
+    for (row, &pivot_column) in pivot_columns.iter().enumerate() {
+        origin[pivot_column] = reduced_row_echelon_form[[row, number_of_unknowns]];
+    }
+    let coefficients = Matrix::new(
+        augmented_matrix.number_of_rows(),
+        number_of_unknowns,
+        |position| augmented_matrix[position],
+    );
+    Some((origin, coefficients.null_space()))
 }
 
 #[cfg(test)]
 mod tests {
+    use itertools::Itertools;
+
     use crate::tests::panic_left_right;
 
     use super::*;
 
+    type Real = f64;
+
     #[test]
     fn exactly_one_solution() {
         let matrix = [
@@ -192,7 +139,36 @@ mod tests {
         assert_solution_sets_approx_eq(actual, expected);
     }
 
-    fn assert_solution_sets_approx_eq(left: SolutionSet, right: SolutionSet) {
+    #[test]
+    fn exact_solution_over_rationals() {
+        let fraction = |numerator| Rational::<i64>::from(numerator);
+        let matrix = [[2, 1, -1, 8], [-3, -1, 2, -11], [-2, 1, 2, -3]].map(|row| row.map(fraction));
+        let expected_origin = [2, 3, -1].map(fraction).to_vec();
+        assert_eq!(solution_set(matrix), Some((expected_origin, vec![])));
+    }
+
+    #[test]
+    fn ill_conditioned_system_is_not_singular_over_rationals() {
+        let tiny = Rational::<i128>::new(1, 10_i128.pow(20));
+        let [zero, one, two] = [0, 1, 2].map(Rational::from);
+        let matrix = [[one, one, two], [one, one + tiny, two + tiny]];
+        assert_eq!(solution_set(matrix), Some((vec![one, one], vec![])));
+        let approximate_matrix = matrix.map(|row| row.map(Rational::to_f64));
+        let (_, directions) =
+            solution_set(approximate_matrix).expect("system should have solutions");
+        assert_eq!(
+            directions.len(),
+            1,
+            "system should appear singular in floating point"
+        );
+        let singular_matrix = [[one, one, two], [two, two, two + two]];
+        assert_eq!(
+            solution_set(singular_matrix),
+            Some((vec![two, zero], vec![vec![-one, one]]))
+        );
+    }
+
+    fn assert_solution_sets_approx_eq(left: SolutionSet<Real>, right: SolutionSet<Real>) {
         match [left, right] {
             [Some((left_origin, left_directions)), Some((right_origin, right_directions))] => {
                 if !vectors_approx_eq(&left_origin, &right_origin) {
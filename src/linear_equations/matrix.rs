@@ -0,0 +1,318 @@
+use std::ops::{Index, IndexMut, Mul};
+
+use itertools::Itertools;
+
+use super::Field;
+
+/// A heap-allocated matrix whose dimensions are only known at runtime, stored in row-major order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Matrix<F> {
+    elements: Vec<F>,
+    number_of_rows: usize,
+    number_of_columns: usize,
+}
+
+impl<F: Field> Matrix<F> {
+    pub fn new(
+        number_of_rows: usize,
+        number_of_columns: usize,
+        mut element: impl FnMut([usize; 2]) -> F,
+    ) -> Self {
+        let elements = (0..number_of_rows)
+            .cartesian_product(0..number_of_columns)
+            .map(|(row, column)| element([row, column]))
+            .collect_vec();
+        Self {
+            elements,
+            number_of_rows,
+            number_of_columns,
+        }
+    }
+
+    pub fn identity(size: usize) -> Self {
+        Self::new(
+            size,
+            size,
+            |[row, column]| {
+                if row == column {
+                    F::one()
+                } else {
+                    F::zero()
+                }
+            },
+        )
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the rows do not all have the same length.
+    pub fn from_rows(rows: impl IntoIterator<Item = impl IntoIterator<Item = F>>) -> Self {
+        let rows = rows
+            .into_iter()
+            .map(|row| row.into_iter().collect_vec())
+            .collect_vec();
+        let number_of_columns = rows.first().map_or(0, Vec::len);
+        assert!(
+            rows.iter().all(|row| row.len() == number_of_columns),
+            "rows should have equal lengths"
+        );
+        Self {
+            number_of_rows: rows.len(),
+            number_of_columns,
+            elements: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    pub fn number_of_rows(&self) -> usize {
+        self.number_of_rows
+    }
+
+    pub fn number_of_columns(&self) -> usize {
+        self.number_of_columns
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self::new(
+            self.number_of_columns,
+            self.number_of_rows,
+            |[row, column]| self[[column, row]],
+        )
+    }
+
+    /// Returns the reduced row echelon form together with the columns that contain its pivots.
+    pub fn reduced_row_echelon_form(&self) -> (Self, Vec<usize>) {
+        let (reduced, pivot_columns, _) = self.eliminate(true);
+        (reduced, pivot_columns)
+    }
+
+    pub fn rank(&self) -> usize {
+        self.reduced_row_echelon_form().1.len()
+    }
+
+    /// Returns a basis of the vectors `x` for which `self * x == 0`, with one basis vector per
+    /// column without a pivot.
+    pub fn null_space(&self) -> Vec<Vec<F>> {
+        let (reduced, pivot_columns) = self.reduced_row_echelon_form();
+        (0..self.number_of_columns)
+            .filter(|column| !pivot_columns.contains(column))
+            .map(|free_column| {
+                let mut vector = vec![F::zero(); self.number_of_columns];
+                vector[free_column] = F::one();
+                for (row, &pivot_column) in pivot_columns.iter().enumerate() {
+                    vector[pivot_column] = -reduced[[row, free_column]];
+                }
+                vector
+            })
+            .collect_vec()
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    pub fn determinant(&self) -> F {
+        assert_eq!(
+            self.number_of_rows, self.number_of_columns,
+            "matrix should be square"
+        );
+        let (echelon, pivot_columns, determinant) = self.eliminate(false);
+        if pivot_columns.len() < self.number_of_rows {
+            return F::zero();
+        }
+        (0..self.number_of_rows).fold(determinant, |product, index| {
+            product * echelon[[index, index]]
+        })
+    }
+
+    /// Returns the inverse, or `None` if the matrix is singular.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    pub fn inverse(&self) -> Option<Self> {
+        let size = self.number_of_rows;
+        assert_eq!(size, self.number_of_columns, "matrix should be square");
+        let augmented = Self::new(size, 2 * size, |[row, column]| {
+            if column < size {
+                self[[row, column]]
+            } else if column - size == row {
+                F::one()
+            } else {
+                F::zero()
+            }
+        });
+        let (reduced, pivot_columns) = augmented.reduced_row_echelon_form();
+        (pivot_columns.len() == size && pivot_columns.iter().all(|&column| column < size))
+            .then(|| Self::new(size, size, |[row, column]| reduced[[row, size + column]]))
+    }
+
+    /// Brings the matrix into row echelon form using partial pivoting, or into reduced row echelon
+    /// form if `reduce` is set. Also returns the pivot columns and the sign change of the
+    /// determinant caused by swapping rows.
+    fn eliminate(&self, reduce: bool) -> (Self, Vec<usize>, F) {
+        let mut matrix = self.clone();
+        let mut pivot_columns = vec![];
+        let mut sign = F::one();
+        for column in 0..self.number_of_columns {
+            let pivot_row = pivot_columns.len();
+            if pivot_row == self.number_of_rows {
+                break;
+            }
+            let preferred_row = (pivot_row..self.number_of_rows)
+                .max_by(|&left, &right| {
+                    let [left, right] =
+                        [left, right].map(|row| matrix[[row, column]].pivot_preference());
+                    left.total_cmp(&right)
+                })
+                .expect("pivot row should be within matrix");
+            let pivot = matrix[[preferred_row, column]];
+            if pivot.is_negligible() {
+                continue;
+            }
+            if preferred_row != pivot_row {
+                matrix.swap_rows(preferred_row, pivot_row);
+                sign = -sign;
+            }
+            let rows = if reduce {
+                0..self.number_of_rows
+            } else {
+                pivot_row + 1..self.number_of_rows
+            };
+            for row in rows.filter(|&row| row != pivot_row) {
+                let ratio = matrix[[row, column]] / pivot;
+                matrix[[row, column]] = F::zero();
+                for other_column in column + 1..self.number_of_columns {
+                    matrix[[row, other_column]] =
+                        matrix[[row, other_column]] - matrix[[pivot_row, other_column]] * ratio;
+                }
+            }
+            if reduce {
+                for other_column in column..self.number_of_columns {
+                    matrix[[pivot_row, other_column]] = matrix[[pivot_row, other_column]] / pivot;
+                }
+            }
+            pivot_columns.push(column);
+        }
+        (matrix, pivot_columns, sign)
+    }
+
+    fn swap_rows(&mut self, left: usize, right: usize) {
+        for column in 0..self.number_of_columns {
+            self.elements.swap(
+                left * self.number_of_columns + column,
+                right * self.number_of_columns + column,
+            );
+        }
+    }
+}
+
+impl<F: Field, const NUM_ROWS: usize, const NUM_COLUMNS: usize> From<[[F; NUM_COLUMNS]; NUM_ROWS]>
+    for Matrix<F>
+{
+    fn from(rows: [[F; NUM_COLUMNS]; NUM_ROWS]) -> Self {
+        Self::from_rows(rows)
+    }
+}
+
+impl<F> Index<[usize; 2]> for Matrix<F> {
+    type Output = F;
+
+    fn index(&self, [row, column]: [usize; 2]) -> &F {
+        debug_assert!(
+            column < self.number_of_columns,
+            "column should be within matrix"
+        );
+        &self.elements[row * self.number_of_columns + column]
+    }
+}
+
+impl<F> IndexMut<[usize; 2]> for Matrix<F> {
+    fn index_mut(&mut self, [row, column]: [usize; 2]) -> &mut F {
+        debug_assert!(
+            column < self.number_of_columns,
+            "column should be within matrix"
+        );
+        &mut self.elements[row * self.number_of_columns + column]
+    }
+}
+
+impl<F: Field> Mul for &Matrix<F> {
+    type Output = Matrix<F>;
+
+    fn mul(self, rhs: Self) -> Matrix<F> {
+        assert_eq!(
+            self.number_of_columns, rhs.number_of_rows,
+            "number of columns of left matrix should equal number of rows of right matrix"
+        );
+        Matrix::new(
+            self.number_of_rows,
+            rhs.number_of_columns,
+            |[row, column]| {
+                (0..self.number_of_columns).fold(F::zero(), |sum, index| {
+                    sum + self[[row, index]] * rhs[[index, column]]
+                })
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rational::Rational;
+
+    type Fraction = Rational<i64>;
+
+    fn matrix<const NUM_ROWS: usize, const NUM_COLUMNS: usize>(
+        rows: [[i64; NUM_COLUMNS]; NUM_ROWS],
+    ) -> Matrix<Fraction> {
+        Matrix::from(rows.map(|row| row.map(Fraction::from)))
+    }
+
+    #[test]
+    fn multiplication_and_transpose() {
+        let left = matrix([[1, 2, 3], [4, 5, 6]]);
+        let right = matrix([[7, 8], [9, 10], [11, 12]]);
+        assert_eq!(&left * &right, matrix([[58, 64], [139, 154]]));
+        assert_eq!(left.transpose(), matrix([[1, 4], [2, 5], [3, 6]]));
+        assert_eq!(&Matrix::identity(2) * &left, left);
+    }
+
+    #[test]
+    fn determinant() {
+        assert_eq!(
+            matrix([[2, 1, -1], [-3, -1, 2], [-2, 1, 2]]).determinant(),
+            Fraction::from(-1)
+        );
+        assert_eq!(matrix([[0, 1], [1, 0]]).determinant(), Fraction::from(-1));
+        assert_eq!(matrix([[1, 2], [2, 4]]).determinant(), Fraction::from(0));
+        let approximate = Matrix::from([[4.0, 3.0], [6.0, 3.0]]).determinant();
+        assert!((approximate + 6.0).abs() < 1e-9, "determinant should be -6");
+    }
+
+    #[test]
+    fn inverse() {
+        let invertible = matrix([[2, 1, -1], [-3, -1, 2], [-2, 1, 2]]);
+        let inverse = invertible
+            .inverse()
+            .expect("matrix with non-zero determinant should be invertible");
+        assert_eq!(&invertible * &inverse, Matrix::identity(3));
+        assert_eq!(matrix([[1, 2], [2, 4]]).inverse(), None);
+    }
+
+    #[test]
+    fn rank_and_null_space() {
+        let singular = matrix([[1, 3, 1], [1, 1, -1], [3, 11, 5]]);
+        assert_eq!(singular.rank(), 2);
+        let null_space = singular.null_space();
+        assert_eq!(null_space, vec![[2, -1, 1].map(Fraction::from).to_vec()]);
+        for vector in null_space {
+            let column = Matrix::from_rows(vector.into_iter().map(|element| [element]));
+            assert_eq!(
+                &singular * &column,
+                Matrix::new(3, 1, |_| Fraction::from(0))
+            );
+        }
+        assert_eq!(matrix([[1, 2], [3, 4]]).rank(), 2);
+        assert!(matrix([[1, 2], [3, 4]]).null_space().is_empty());
+    }
+}
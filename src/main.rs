@@ -42,20 +42,35 @@
 
 mod advent2022;
 mod advent2023;
+mod bit_grid;
+mod dot;
 mod floating_point;
+mod geometry;
+mod graph;
 mod grid;
+mod image;
+mod intervals;
 mod linear_equations;
+mod manhattan;
+mod number_theory;
+mod orthotope;
+mod polynomial;
+mod rational;
+mod rotation;
 mod search;
+mod simulation;
 mod string;
 mod vector;
 
-use std::{fmt::Debug, fs, ops::RangeInclusive};
+use std::{fmt::Debug, fs, ops::RangeInclusive, path::PathBuf};
 
 use ahash::{AHashMap as HashMap, AHashSet as HashSet};
 use anyhow::{anyhow, Ok, Result};
 use clap::Parser;
 use strum::EnumString;
 
+use crate::{dot::Dot, image::Image, simulation::Simulation};
+
 fn usize_within(range: RangeInclusive<usize>, str: &str) -> Result<usize> {
     let usize = str
         .parse()
@@ -83,6 +98,11 @@ fn day(str: &str) -> Result<Day> {
     usize_within(1..=25, str)
 }
 
+fn frames_per_second(str: &str) -> Result<u16> {
+    let frames_per_second = usize_within(1..=1000, str)?;
+    Ok(frames_per_second.try_into()?)
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumString)]
 pub enum Puzzle {
     #[strum(ascii_case_insensitive)]
@@ -92,7 +112,7 @@ pub enum Puzzle {
     Second,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Parser)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Parser)]
 #[command(about, long_about = None)]
 struct CommandLineArguments {
     /// Which year?
@@ -105,6 +125,30 @@ struct CommandLineArguments {
 
     /// First or second puzzle?
     puzzle: Puzzle,
+
+    /// Save an image of the puzzle input as PPM, PNG or SVG
+    #[clap(long, value_name = "PATH")]
+    image: Option<PathBuf>,
+
+    /// Save the graph built from the puzzle input in Graphviz DOT format
+    #[clap(long, value_name = "PATH")]
+    dot: Option<PathBuf>,
+
+    /// Play a step-by-step simulation of the puzzle in the terminal
+    #[clap(long)]
+    visualize: bool,
+
+    /// Save the simulation as an animated PNG
+    #[clap(long, value_name = "PATH")]
+    record: Option<PathBuf>,
+
+    /// Frames per second of the simulation
+    #[clap(long, value_parser=frames_per_second, default_value = "10", value_name = "FPS")]
+    speed: u16,
+
+    /// Maximum number of frames to record
+    #[clap(long, default_value_t = 500)]
+    frames: usize,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -132,6 +176,39 @@ fn solution(year: usize, day: usize, puzzle: Puzzle) -> Solution {
     solution(day, puzzle)
 }
 
+type Drawing = fn(&str) -> Image;
+
+fn drawing(year: usize, day: usize) -> Option<Drawing> {
+    let drawing = match year {
+        2022 => advent2022::drawing,
+        2023 => advent2023::drawing,
+        _ => panic!("year should be 2022 or 2023"),
+    };
+    drawing(day)
+}
+
+type Diagram = fn(&str) -> Dot;
+
+fn diagram(year: usize, day: usize) -> Option<Diagram> {
+    let diagram = match year {
+        2022 => advent2022::diagram,
+        2023 => advent2023::diagram,
+        _ => panic!("year should be 2022 or 2023"),
+    };
+    diagram(day)
+}
+
+type Animation = fn(&str) -> Box<dyn Simulation + '_>;
+
+fn animation(year: usize, day: usize) -> Option<Animation> {
+    let animation = match year {
+        2022 => advent2022::animation,
+        2023 => advent2023::animation,
+        _ => panic!("year should be 2022 or 2023"),
+    };
+    animation(day)
+}
+
 fn main() {
     let command_line_arguments = CommandLineArguments::parse();
 
@@ -147,6 +224,35 @@ fn main() {
     );
     let answer = solution(&input);
     println!("{answer}");
+
+    if let Some(path) = command_line_arguments.image {
+        let drawing = drawing(command_line_arguments.year, command_line_arguments.day)
+            .expect("an image should be available for the given day");
+        drawing(&input).save(path).expect("image should be saved");
+    }
+
+    if let Some(path) = command_line_arguments.dot {
+        let diagram = diagram(command_line_arguments.year, command_line_arguments.day)
+            .expect("a graph should be available for the given day");
+        fs::write(path, diagram(&input).to_string()).expect("graph should be saved");
+    }
+
+    if command_line_arguments.visualize || command_line_arguments.record.is_some() {
+        let animation = animation(command_line_arguments.year, command_line_arguments.day)
+            .expect("a simulation should be available for the given day");
+        if command_line_arguments.visualize {
+            simulation::play(&mut *animation(&input), command_line_arguments.speed);
+        }
+        if let Some(path) = command_line_arguments.record {
+            simulation::record(
+                &mut *animation(&input),
+                path,
+                command_line_arguments.frames,
+                command_line_arguments.speed,
+            )
+            .expect("animation should be saved");
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,285 @@
+use std::{
+    cmp,
+    ops::{Range, RangeInclusive},
+};
+
+use itertools::Itertools;
+
+use crate::{
+    orthotope::{self, Orthotope},
+    vector::Vector,
+};
+
+pub type Coordinate = orthotope::Coordinate;
+pub type Point = [Coordinate; 2];
+
+pub fn distance<const N: usize>(left: [Coordinate; N], right: [Coordinate; N]) -> Coordinate {
+    left.sub(right).manhattan()
+}
+
+/// Maps `[a, b]` to `[a + b, a - b]`, which turns diamonds into axis-aligned squares and Manhattan
+/// distances into Chebyshev distances.
+pub fn rotate([a, b]: Point) -> Point {
+    [a + b, a - b]
+}
+
+/// Inverts [`rotate`], or returns `None` if the point is not the rotation of an integer point,
+/// which is the case if its coordinates differ in parity.
+// `Coverage::uncovered_point` searches the original coordinates of a rotated piece instead.
+#[allow(dead_code)]
+pub fn unrotate([sum, difference]: Point) -> Option<Point> {
+    ((sum - difference) % 2 == 0).then(|| [(sum + difference) / 2, (sum - difference) / 2])
+}
+
+/// The points within a Manhattan distance of `radius` from `center`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Diamond {
+    center: Point,
+    radius: Coordinate,
+}
+
+impl Diamond {
+    pub fn new(center: Point, radius: Coordinate) -> Self {
+        assert!(radius >= 0, "radius should not be negative");
+        Self { center, radius }
+    }
+
+    // 2022 day 15 takes the cross section of every diamond rather than testing positions one by one.
+    #[allow(dead_code)]
+    pub fn contains(&self, point: Point) -> bool {
+        distance(self.center, point) <= self.radius
+    }
+
+    /// Returns the second coordinates of the points whose first coordinate is `first`.
+    pub fn cross_section(&self, first: Coordinate) -> Option<Range<Coordinate>> {
+        let [center_first, center_second] = self.center;
+        let half_width = self.radius - (center_first - first).abs();
+        (half_width >= 0).then(|| center_second - half_width..center_second + half_width + 1)
+    }
+
+    /// Returns the square that the diamond turns into under [`rotate`]. The square also contains
+    /// points that are not the rotation of an integer point.
+    pub fn rotated(&self) -> Orthotope<2> {
+        let [sum, difference] = rotate(self.center);
+        Orthotope::new(
+            [sum, difference].map(|center| center - self.radius..center + self.radius + 1),
+        )
+    }
+}
+
+/// The union of diamonds, stored as disjoint squares in rotated coordinates.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Coverage {
+    rotated: Vec<Orthotope<2>>,
+}
+
+impl Coverage {
+    // The distress beacon of 2022 day 15 is found as an uncovered point, not by probing.
+    #[allow(dead_code)]
+    pub fn contains(&self, point: Point) -> bool {
+        let rotated = rotate(point);
+        self.rotated
+            .iter()
+            .any(|orthotope| orthotope.contains(&rotated))
+    }
+
+    /// Returns the number of integer points that are covered.
+    // 2022 day 15 counts the impossible positions of a single row with an `IntervalSet`.
+    #[allow(dead_code)]
+    pub fn number_of_points(&self) -> usize {
+        self.rotated
+            .iter()
+            .map(|orthotope| {
+                let [sums, differences] = orthotope.ranges().clone().map(parity_counts);
+                sums[0] * differences[0] + sums[1] * differences[1]
+            })
+            .sum()
+    }
+
+    /// Returns a point within the region, given by the inclusive range of either coordinate, that
+    /// is not covered.
+    pub fn uncovered_point(&self, region: &[RangeInclusive<Coordinate>; 2]) -> Option<Point> {
+        let [first, second] = region;
+        let corners = [*first.start(), *first.end()]
+            .into_iter()
+            .cartesian_product([*second.start(), *second.end()])
+            .map(|(first, second)| rotate([first, second]))
+            .collect_vec();
+        let bounds = Orthotope::new([0, 1].map(|axis| {
+            let (min, max) = corners
+                .iter()
+                .map(|corner| corner[axis])
+                .minmax()
+                .into_option()
+                .expect("region should have corners");
+            min..max + 1
+        }));
+        let mut uncovered = vec![bounds];
+        for square in &self.rotated {
+            uncovered = uncovered
+                .iter()
+                .flat_map(|piece| piece.subtract(square))
+                .collect();
+        }
+        uncovered
+            .iter()
+            .find_map(|piece| point_within(piece, region))
+    }
+}
+
+impl FromIterator<Diamond> for Coverage {
+    fn from_iter<I: IntoIterator<Item = Diamond>>(diamonds: I) -> Self {
+        let squares = diamonds
+            .into_iter()
+            .map(|diamond| diamond.rotated())
+            .collect_vec();
+        Self {
+            rotated: orthotope::disjoint_union(&squares),
+        }
+    }
+}
+
+/// Returns the number of even and odd values in the range.
+fn parity_counts(range: Range<Coordinate>) -> [usize; 2] {
+    let evens = (range.end + 1).div_euclid(2) - (range.start + 1).div_euclid(2);
+    let odds = range.end - range.start - evens;
+    [evens, odds].map(|count| usize::try_from(count).expect("count should not be negative"))
+}
+
+/// Returns a point `[a, b]` within `region` whose rotation lies within `rotated`.
+///
+/// # Correctness
+///
+/// For a fixed `a`, the feasible `b` lie between the largest of the lower bounds `b >= min_second`,
+/// `b >= min_sum - a` and `b >= a - max_difference` and the smallest of the corresponding upper
+/// bounds. The width between them is concave and piecewise linear in `a`, so over the integers it
+/// is largest next to one of the points where two bounds on the same side cross, or at the ends of
+/// the range of `a`.
+fn point_within(
+    rotated: &Orthotope<2>,
+    [first, second]: &[RangeInclusive<Coordinate>; 2],
+) -> Option<Point> {
+    let [sums, differences] = rotated.ranges();
+    let [min_sum, max_sum] = [sums.start, sums.end - 1];
+    let [min_difference, max_difference] = [differences.start, differences.end - 1];
+    let [min_second, max_second] = [*second.start(), *second.end()];
+    let seconds = |a: Coordinate| {
+        let min = cmp::max(min_second, cmp::max(min_sum - a, a - max_difference));
+        let max = cmp::min(max_second, cmp::min(max_sum - a, a - min_difference));
+        min..=max
+    };
+    let crossings = [
+        min_sum - min_second,
+        min_second + max_difference,
+        max_sum - max_second,
+        max_second + min_difference,
+    ];
+    let halved_crossings = [min_sum + max_difference, max_sum + min_difference]
+        .into_iter()
+        .flat_map(|doubled| [doubled.div_euclid(2), doubled.div_euclid(2) + 1]);
+    [*first.start(), *first.end()]
+        .into_iter()
+        .chain(crossings)
+        .chain(halved_crossings)
+        .filter(|a| first.contains(a))
+        .find_map(|a| {
+            let seconds = seconds(a);
+            (!seconds.is_empty()).then(|| [a, *seconds.start()])
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    use super::*;
+    use crate::tests::test_cases;
+
+    fn random_coverage(rng: &mut SmallRng) -> (Vec<Diamond>, Coverage) {
+        let diamonds = (0..rng.gen_range(0..8))
+            .map(|_| {
+                let center = [0, 1].map(|_| rng.gen_range(-5..15));
+                Diamond::new(center, rng.gen_range(0..6))
+            })
+            .collect_vec();
+        let coverage = diamonds.iter().copied().collect();
+        (diamonds, coverage)
+    }
+
+    fn points(range: RangeInclusive<Coordinate>) -> impl Iterator<Item = Point> + Clone {
+        range
+            .clone()
+            .cartesian_product(range)
+            .map(|(first, second)| [first, second])
+    }
+
+    #[test]
+    fn rotation() {
+        for point in points(-3..=3) {
+            assert_eq!(unrotate(rotate(point)), Some(point));
+        }
+        assert_eq!(unrotate([1, 0]), None);
+        assert_eq!(distance([1, -2, 3], [-1, 2, 3]), 6);
+    }
+
+    #[test]
+    fn cross_section() {
+        let diamond = Diamond::new([7, 8], 9);
+        let cases = [
+            (-3, None),
+            (-2, Some(8..9)),
+            (3, Some(3..14)),
+            (7, Some(-1..18)),
+            (10, Some(2..15)),
+            (14, Some(6..11)),
+            (17, None),
+        ];
+        test_cases(|first| diamond.cross_section(first), cases);
+    }
+
+    #[test]
+    fn coverage_matches_diamonds() {
+        let mut rng = SmallRng::seed_from_u64(15);
+        for _ in 0..200 {
+            let (diamonds, coverage) = random_coverage(&mut rng);
+            let covered = points(-15..=25)
+                .filter(|&point| diamonds.iter().any(|diamond| diamond.contains(point)))
+                .collect_vec();
+            assert_eq!(coverage.number_of_points(), covered.len());
+            for point in points(-15..=25) {
+                assert_eq!(
+                    coverage.contains(point),
+                    covered.contains(&point),
+                    "coverage of {point:?} should match"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn uncovered_point_matches_brute_force() {
+        let mut rng = SmallRng::seed_from_u64(48);
+        for _ in 0..1000 {
+            let (diamonds, coverage) = random_coverage(&mut rng);
+            let [first_start, second_start] = [0, 1].map(|_| rng.gen_range(-5..10));
+            let region =
+                [first_start, second_start].map(|start| start..=start + rng.gen_range(0..8));
+            let is_uncovered =
+                |point: Point| !diamonds.iter().any(|diamond| diamond.contains(point));
+            let number_of_uncovered_points = region[0]
+                .clone()
+                .cartesian_product(region[1].clone())
+                .filter(|&(first, second)| is_uncovered([first, second]))
+                .count();
+            match coverage.uncovered_point(&region) {
+                None => assert_eq!(number_of_uncovered_points, 0),
+                Some(point @ [first, second]) => assert!(
+                    region[0].contains(&first)
+                        && region[1].contains(&second)
+                        && is_uncovered(point),
+                    "{point:?} should be an uncovered point within the region"
+                ),
+            }
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use num_traits::PrimInt;
+
+pub type Integer = i128;
+
+/// Returns the non-negative greatest common divisor, which is zero only if both values are zero.
+pub fn gcd<T: PrimInt>(left: T, right: T) -> T {
+    let [mut left, mut right] = [left, right];
+    while !right.is_zero() {
+        [left, right] = [right, left % right];
+    }
+    if left < T::zero() {
+        T::zero() - left
+    } else {
+        left
+    }
+}
+
+/// Returns the non-negative least common multiple, which is zero if either value is zero.
+pub fn lcm<T: PrimInt>(left: T, right: T) -> T {
+    if left.is_zero() || right.is_zero() {
+        return T::zero();
+    }
+    let multiple = (left / gcd(left, right))
+        .checked_mul(&right)
+        .expect("least common multiple should not overflow");
+    if multiple < T::zero() {
+        T::zero() - multiple
+    } else {
+        multiple
+    }
+}
+
+pub fn lcm_all<T: PrimInt>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::one(), lcm)
+}
+
+/// Returns `(gcd, x, y)` such that `left * x + right * y == gcd`.
+pub fn extended_gcd(left: Integer, right: Integer) -> (Integer, Integer, Integer) {
+    let [mut old_remainder, mut remainder] = [left, right];
+    let [mut old_x, mut x] = [1, 0];
+    let [mut old_y, mut y] = [0, 1];
+    while remainder != 0 {
+        let quotient = old_remainder / remainder;
+        [old_remainder, remainder] = [remainder, old_remainder - quotient * remainder];
+        [old_x, x] = [x, old_x - quotient * x];
+        [old_y, y] = [y, old_y - quotient * y];
+    }
+    if old_remainder < 0 {
+        (-old_remainder, -old_x, -old_y)
+    } else {
+        (old_remainder, old_x, old_y)
+    }
+}
+
+/// Returns the `x` in `0..modulus` with `value * x ≡ 1 (mod modulus)`, or `None` if `value` and
+/// `modulus` are not coprime.
+// `chinese_remainder` solves for its congruences with `extended_gcd` directly.
+#[allow(dead_code)]
+pub fn mod_inverse(value: Integer, modulus: Integer) -> Option<Integer> {
+    let (divisor, x, _) = extended_gcd(value, modulus);
+    (divisor == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// Returns `base` to the power of `exponent` modulo `modulus`, which should be below `2^63` for the
+/// intermediate products to fit.
+// None of the puzzles so far raise numbers to large powers under a modulus.
+#[allow(dead_code)]
+pub fn mod_pow(base: Integer, mut exponent: u64, modulus: Integer) -> Integer {
+    let mut base = base.rem_euclid(modulus);
+    let mut power = 1 % modulus;
+    while exponent > 0 {
+        if exponent % 2 == 1 {
+            power = power * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent /= 2;
+    }
+    power
+}
+
+/// Returns the largest integer whose square does not exceed `value`.
+pub fn isqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+    let [mut root, mut next] = [value, value / 2 + value % 2];
+    while next < root {
+        root = next;
+        next = (root + value / root) / 2;
+    }
+    root
+}
+
+/// Solves the congruences `x ≡ residue (mod modulus)`, whose moduli need not be coprime. Returns the
+/// solutions as a single `(residue, modulus)` whose modulus is the least common multiple of all
+/// moduli, or `None` if the congruences contradict each other.
+pub fn chinese_remainder(
+    congruences: impl IntoIterator<Item = (Integer, Integer)>,
+) -> Option<(Integer, Integer)> {
+    congruences.into_iter().try_fold(
+        (0, 1),
+        |(residue, modulus), (other_residue, other_modulus)| {
+            let (divisor, x, _) = extended_gcd(modulus, other_modulus);
+            let difference = other_residue - residue;
+            if difference % divisor != 0 {
+                return None;
+            }
+            let combined_modulus = modulus / divisor * other_modulus;
+            let steps =
+                (difference / divisor % (other_modulus / divisor)) * x % (other_modulus / divisor);
+            let combined_residue = (residue + modulus * steps).rem_euclid(combined_modulus);
+            Some((combined_residue, combined_modulus))
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn divisors_and_multiples() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(lcm(4_usize, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        assert_eq!(lcm_all([2_u64, 3, 4, 5]), 60);
+        assert_eq!(lcm_all(Vec::<u64>::new()), 1);
+    }
+
+    #[test]
+    fn bezout_coefficients() {
+        let mut rng = SmallRng::seed_from_u64(43);
+        for _ in 0..1000 {
+            let [left, right] = [0, 1].map(|_| rng.gen_range(-1000..1000));
+            let (divisor, x, y) = extended_gcd(left, right);
+            assert_eq!(divisor, gcd(left, right));
+            assert_eq!(left * x + right * y, divisor);
+        }
+    }
+
+    #[test]
+    fn modular_inverse_and_power() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(mod_pow(3, 200, 1_000_000_007), 136_318_165);
+        assert_eq!(mod_pow(-2, 3, 5), 2);
+        assert_eq!(mod_pow(7, 0, 1), 0);
+    }
+
+    #[test]
+    fn integer_square_root() {
+        for value in 0..10_000 {
+            let root = isqrt(value);
+            assert!(
+                root * root <= value && (root + 1) * (root + 1) > value,
+                "{root} should be integer square root of {value}"
+            );
+        }
+        assert_eq!(isqrt(u128::MAX), u128::from(u64::MAX));
+        assert_eq!(isqrt(10_u128.pow(36)), 10_u128.pow(18));
+        assert_eq!(isqrt(10_u128.pow(36) - 1), 10_u128.pow(18) - 1);
+    }
+
+    #[test]
+    fn chinese_remainder_matches_brute_force() {
+        assert_eq!(chinese_remainder([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(chinese_remainder([(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(chinese_remainder([(1, 4), (2, 6)]), None);
+        let mut rng = SmallRng::seed_from_u64(8);
+        for _ in 0..1000 {
+            let congruences = (0..rng.gen_range(1..4))
+                .map(|_| {
+                    let modulus = rng.gen_range(1..13);
+                    (rng.gen_range(-20..20), modulus)
+                })
+                .collect::<Vec<(Integer, Integer)>>();
+            let modulus = lcm_all(congruences.iter().map(|&(_, modulus)| modulus));
+            let solutions = (0..modulus)
+                .filter(|x| {
+                    congruences
+                        .iter()
+                        .all(|&(residue, modulus)| (x - residue).rem_euclid(modulus) == 0)
+                })
+                .collect::<Vec<_>>();
+            let expected = match solutions[..] {
+                [] => None,
+                [solution] => Some((solution, modulus)),
+                _ => panic!("solution should be unique modulo least common multiple"),
+            };
+            assert_eq!(chinese_remainder(congruences), expected);
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use std::{array, cmp, ops::Range};
+
+use easy_cast::Conv;
+
+pub type Coordinate = i64;
+pub type Volume = u64;
+
+/// An axis-aligned box in `N` dimensions, the product of `N` half-open ranges.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Orthotope<const N: usize> {
+    ranges: [Range<Coordinate>; N],
+}
+
+impl<const N: usize> Orthotope<N> {
+    pub fn new(ranges: [Range<Coordinate>; N]) -> Self {
+        Self { ranges }
+    }
+
+    pub fn ranges(&self) -> &[Range<Coordinate>; N] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.iter().any(|range| range.start >= range.end)
+    }
+
+    // 2023 day 19 only measures the volume of its rating boxes, never tests single parts against them.
+    #[allow(dead_code)]
+    pub fn contains(&self, point: &[Coordinate; N]) -> bool {
+        self.ranges
+            .iter()
+            .zip(point)
+            .all(|(range, coordinate)| range.contains(coordinate))
+    }
+
+    pub fn volume(&self) -> Volume {
+        if self.is_empty() {
+            return 0;
+        }
+        self.ranges
+            .iter()
+            .map(|range| Volume::conv(range.end - range.start))
+            .product()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let intersection = Self::new(array::from_fn(|axis| {
+            let [range, other] = [&self.ranges[axis], &other.ranges[axis]];
+            cmp::max(range.start, other.start)..cmp::min(range.end, other.end)
+        }));
+        (!intersection.is_empty()).then_some(intersection)
+    }
+
+    /// Returns at most `2 * N` disjoint boxes that together cover the points of `self` outside
+    /// `other`.
+    pub fn subtract(&self, other: &Self) -> Vec<Self> {
+        if self.is_empty() {
+            return vec![];
+        }
+        let Some(intersection) = self.intersection(other) else {
+            return vec![self.clone()];
+        };
+        let mut pieces = vec![];
+        let mut remainder = self.clone();
+        for axis in 0..N {
+            let [range, inner] = [&remainder.ranges[axis], &intersection.ranges[axis]];
+            for outer in [range.start..inner.start, inner.end..range.end] {
+                if outer.start < outer.end {
+                    let mut piece = remainder.clone();
+                    piece.ranges[axis] = outer;
+                    pieces.push(piece);
+                }
+            }
+            remainder.ranges[axis] = intersection.ranges[axis].clone();
+        }
+        pieces
+    }
+}
+
+/// Returns disjoint orthotopes that together cover the same points as the possibly overlapping
+/// `orthotopes`.
+pub fn disjoint_union<'orthotope, const N: usize>(
+    orthotopes: impl IntoIterator<Item = &'orthotope Orthotope<N>>,
+) -> Vec<Orthotope<N>> {
+    let mut disjoint: Vec<Orthotope<N>> = vec![];
+    for orthotope in orthotopes {
+        let mut pieces = vec![orthotope.clone()];
+        for existing in &disjoint {
+            pieces = pieces
+                .iter()
+                .flat_map(|piece| piece.subtract(existing))
+                .collect();
+            if pieces.is_empty() {
+                break;
+            }
+        }
+        disjoint.extend(pieces);
+    }
+    disjoint
+}
+
+/// Returns the volume covered by at least one of the possibly overlapping `orthotopes`.
+pub fn union_volume<'orthotope, const N: usize>(
+    orthotopes: impl IntoIterator<Item = &'orthotope Orthotope<N>>,
+) -> Volume {
+    disjoint_union(orthotopes)
+        .iter()
+        .map(Orthotope::volume)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    use super::*;
+
+    fn random_orthotope(rng: &mut SmallRng) -> Orthotope<3> {
+        Orthotope::new(array::from_fn(|_| {
+            let start = rng.gen_range(-5..5);
+            start..start + rng.gen_range(0..6)
+        }))
+    }
+
+    fn points() -> impl Iterator<Item = [Coordinate; 3]> {
+        (0..3)
+            .map(|_| -5..10)
+            .multi_cartesian_product()
+            .map(|point| {
+                point
+                    .try_into()
+                    .expect("point should have three coordinates")
+            })
+    }
+
+    #[test]
+    fn volume_and_intersection() {
+        let left = Orthotope::new([0..4, 0..3]);
+        let right = Orthotope::new([2..6, -1..1]);
+        assert_eq!(left.volume(), 12);
+        assert_eq!(
+            left.intersection(&right),
+            Some(Orthotope::new([2..4, 0..1]))
+        );
+        assert_eq!(left.intersection(&Orthotope::new([4..5, 0..3])), None);
+        assert_eq!(Orthotope::new([0..4, 3..3]).volume(), 0);
+    }
+
+    #[test]
+    fn subtraction_is_disjoint_and_exact() {
+        let mut rng = SmallRng::seed_from_u64(40);
+        for _ in 0..500 {
+            let [left, right] = [random_orthotope(&mut rng), random_orthotope(&mut rng)];
+            let pieces = left.subtract(&right);
+            assert!(
+                pieces.len() <= 6,
+                "there should be at most two pieces per axis"
+            );
+            for point in points() {
+                let number_of_pieces = pieces.iter().filter(|piece| piece.contains(&point)).count();
+                let expected = usize::from(left.contains(&point) && !right.contains(&point));
+                assert_eq!(
+                    number_of_pieces, expected,
+                    "point {point:?} should be covered once"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn union_volume_matches_counting_points() {
+        let mut rng = SmallRng::seed_from_u64(19);
+        for _ in 0..100 {
+            let orthotopes = (0..rng.gen_range(0..8))
+                .map(|_| random_orthotope(&mut rng))
+                .collect_vec();
+            let expected = points()
+                .filter(|point| orthotopes.iter().any(|orthotope| orthotope.contains(point)))
+                .count();
+            assert_eq!(union_volume(&orthotopes), Volume::conv(expected));
+        }
+    }
+}
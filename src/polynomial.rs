@@ -0,0 +1,249 @@
+use std::ops::Range;
+
+use itertools::Itertools;
+use num_traits::{PrimInt, Signed, Zero};
+
+use crate::{
+    number_theory::{isqrt, Integer},
+    rational::Rational,
+};
+
+/// A polynomial with exact rational coefficients, stored from the constant term upwards without
+/// trailing zeros.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Polynomial<T> {
+    coefficients: Vec<Rational<T>>,
+}
+
+impl<T: PrimInt + Signed> Polynomial<T> {
+    pub fn new(coefficients: impl IntoIterator<Item = Rational<T>>) -> Self {
+        let mut coefficients = coefficients.into_iter().collect_vec();
+        while coefficients.last().is_some_and(Zero::is_zero) {
+            coefficients.pop();
+        }
+        Self { coefficients }
+    }
+
+    /// Returns the polynomial of lowest degree that passes through all `(x, y)` points, using
+    /// Lagrange interpolation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if two points share the same `x`.
+    pub fn interpolate(points: impl IntoIterator<Item = (T, T)>) -> Self {
+        let points = points.into_iter().collect_vec();
+        let mut coefficients = vec![Rational::zero(); points.len()];
+        for (index, &(x, y)) in points.iter().enumerate() {
+            let mut basis = vec![Rational::from(T::one())];
+            let mut denominator = Rational::from(T::one());
+            for (_, &(other_x, _)) in points.iter().enumerate().filter(|&(i, _)| i != index) {
+                assert!(x != other_x, "points should have distinct x");
+                basis = multiply_by_linear_factor(&basis, Rational::from(other_x));
+                denominator = denominator * Rational::from(x - other_x);
+            }
+            let scale = Rational::from(y) / denominator;
+            for (coefficient, basis_coefficient) in coefficients.iter_mut().zip(basis) {
+                *coefficient = *coefficient + basis_coefficient * scale;
+            }
+        }
+        Self::new(coefficients)
+    }
+
+    // 2023 day 21 only evaluates its interpolated polynomial.
+    #[allow(dead_code)]
+    pub fn coefficients(&self) -> &[Rational<T>] {
+        &self.coefficients
+    }
+
+    /// Returns the degree, or `None` for the zero polynomial.
+    // Interpolating three samples of 2023 day 21 always gives at most a quadratic.
+    #[allow(dead_code)]
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    pub fn evaluate(&self, x: Rational<T>) -> Rational<T> {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(Rational::zero(), |value, &coefficient| {
+                value * x + coefficient
+            })
+    }
+}
+
+/// Returns the coefficients of `polynomial * (x - root)`.
+fn multiply_by_linear_factor<T: PrimInt + Signed>(
+    polynomial: &[Rational<T>],
+    root: Rational<T>,
+) -> Vec<Rational<T>> {
+    let mut product = vec![Rational::zero(); polynomial.len() + 1];
+    for (index, &coefficient) in polynomial.iter().enumerate() {
+        product[index + 1] = product[index + 1] + coefficient;
+        product[index] = product[index] - coefficient * root;
+    }
+    product
+}
+
+/// The Newton form of the polynomial through the values `f(0), f(1), ...` of a sequence, given by
+/// the leading forward differences `Δ^k f(0)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ForwardDifferences<T> {
+    leading_differences: Vec<T>,
+}
+
+impl<T: PrimInt + Signed> ForwardDifferences<T> {
+    pub fn new(values: &[T]) -> Self {
+        let mut row = values.to_vec();
+        let mut leading_differences = vec![];
+        while let Some(&first) = row.first() {
+            leading_differences.push(first);
+            row = row.windows(2).map(|pair| pair[1] - pair[0]).collect_vec();
+        }
+        while leading_differences.last().is_some_and(Zero::is_zero) {
+            leading_differences.pop();
+        }
+        Self {
+            leading_differences,
+        }
+    }
+
+    /// Returns the degree of the polynomial of lowest degree through the values, or `None` if all
+    /// values are zero. The polynomial only predicts the sequence beyond its values if its degree
+    /// is less than the number of values minus one.
+    pub fn degree(&self) -> Option<usize> {
+        self.leading_differences.len().checked_sub(1)
+    }
+
+    /// Returns `f(x)` for any integer `x`, including negative ones and those far beyond the values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an intermediate value overflows.
+    pub fn evaluate(&self, x: T) -> T {
+        let mut value = T::zero();
+        let mut binomial = T::one();
+        let mut k = T::zero();
+        for (index, &difference) in self.leading_differences.iter().enumerate() {
+            if index > 0 {
+                binomial = binomial
+                    .checked_mul(&(x - k))
+                    .expect("binomial coefficient should not overflow");
+                k = k + T::one();
+                binomial = binomial / k;
+            }
+            let term = difference
+                .checked_mul(&binomial)
+                .expect("term should not overflow");
+            value = value.checked_add(&term).expect("value should not overflow");
+        }
+        value
+    }
+}
+
+/// Returns the integers `x` with `a * x^2 + b * x + c < 0`, which lie strictly between the roots.
+///
+/// # Panics
+///
+/// Panics if `a` is not positive, or if the discriminant overflows.
+pub fn quadratic_negative_range(a: Integer, b: Integer, c: Integer) -> Range<Integer> {
+    assert!(a > 0, "leading coefficient should be positive");
+    let discriminant = b
+        .checked_mul(b)
+        .zip(a.checked_mul(c).and_then(|ac| ac.checked_mul(4)))
+        .and_then(|(bb, four_ac)| bb.checked_sub(four_ac))
+        .expect("discriminant should not overflow");
+    if discriminant <= 0 {
+        return 0..0;
+    }
+    // The polynomial is negative if and only if `(2 * a * x + b)^2 < discriminant`.
+    let bound =
+        Integer::try_from(isqrt(discriminant.unsigned_abs() - 1)).expect("square root should fit");
+    let start = -(b + bound).div_euclid(2 * a);
+    let end = (bound - b).div_euclid(2 * a) + 1;
+    start..end.max(start)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    use super::*;
+
+    type Fraction = Rational<i64>;
+
+    #[test]
+    fn lagrange_interpolation() {
+        let polynomial = Polynomial::interpolate([(1, 6), (-1, 2), (2, 11)]);
+        assert_eq!(polynomial.degree(), Some(2));
+        assert_eq!(
+            polynomial.coefficients(),
+            [3, 2, 1].map(Fraction::from).as_slice()
+        );
+        let halves = Polynomial::interpolate([(0, 0), (1, 0), (2, 1)]);
+        assert_eq!(
+            halves.coefficients(),
+            [Fraction::zero(), Fraction::new(-1, 2), Fraction::new(1, 2)]
+        );
+        assert_eq!(halves.evaluate(Fraction::from(4)), Fraction::from(6));
+        assert_eq!(Polynomial::interpolate([(3, 0), (5, 0)]).degree(), None);
+        assert_eq!(Polynomial::<i64>::interpolate([]).degree(), None);
+    }
+
+    #[test]
+    fn forward_differences() {
+        let differences = ForwardDifferences::new(&[1, 3, 6, 10, 15, 21]);
+        assert_eq!(differences.degree(), Some(2));
+        assert_eq!(differences.evaluate(6), 28);
+        assert_eq!(differences.evaluate(-1), 0);
+        assert_eq!(differences.evaluate(-3), 1);
+        assert_eq!(ForwardDifferences::new(&[4, 4, 4]).degree(), Some(0));
+        assert_eq!(ForwardDifferences::new(&[0, 0]).degree(), None);
+        let large = ForwardDifferences::<i128>::new(&[0, 1, 8, 27]);
+        assert_eq!(large.evaluate(10_i128.pow(12)), 10_i128.pow(36));
+    }
+
+    #[test]
+    fn quadratic_negative_range_matches_brute_force() {
+        assert_eq!(quadratic_negative_range(1, -7, 9), 2..6);
+        assert_eq!(quadratic_negative_range(1, -30, 200), 11..20);
+        assert_eq!(quadratic_negative_range(1, 0, 0), 0..0);
+        let mut rng = SmallRng::seed_from_u64(6);
+        for _ in 0..2000 {
+            let [a, b, c] = [
+                rng.gen_range(1..10),
+                rng.gen_range(-50..50),
+                rng.gen_range(-50..50),
+            ];
+            let expected = (-100..100)
+                .filter(|x| a * x * x + b * x + c < 0)
+                .collect_vec();
+            let range = quadratic_negative_range(a, b, c);
+            assert_eq!(
+                range.clone().collect_vec(),
+                expected,
+                "range of {a}x^2 + {b}x + {c} should match"
+            );
+        }
+    }
+
+    #[test]
+    fn newton_and_lagrange_forms_agree() {
+        let mut rng = SmallRng::seed_from_u64(44);
+        for _ in 0..200 {
+            let values = (0..rng.gen_range(0..7))
+                .map(|_| rng.gen_range(-50..50))
+                .collect_vec();
+            let differences = ForwardDifferences::new(&values);
+            let polynomial = Polynomial::interpolate((0..).zip(values.iter().copied()));
+            assert_eq!(differences.degree(), polynomial.degree());
+            for x in -10..10 {
+                assert_eq!(
+                    Fraction::from(differences.evaluate(x)),
+                    polynomial.evaluate(Fraction::from(x)),
+                    "forms should agree at {x}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use num_traits::{One, PrimInt, Signed, Zero};
+
+use crate::number_theory::gcd;
+
+/// A fraction in lowest terms with a positive denominator. Arithmetic panics on overflow instead of
+/// losing precision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational<T> {
+    numerator: T,
+    denominator: T,
+}
+
+impl<T: PrimInt + Signed> Rational<T> {
+    pub fn new(numerator: T, denominator: T) -> Self {
+        assert!(!denominator.is_zero(), "denominator should not be zero");
+        let divisor = gcd(numerator, denominator);
+        let sign = denominator.signum();
+        Self {
+            numerator: numerator / divisor * sign,
+            denominator: denominator / divisor * sign,
+        }
+    }
+
+    pub fn numerator(self) -> T {
+        self.numerator
+    }
+
+    pub fn denominator(self) -> T {
+        self.denominator
+    }
+
+    /// Returns the value as an integer if the denominator is one.
+    pub fn to_integer(self) -> Option<T> {
+        self.denominator.is_one().then_some(self.numerator)
+    }
+
+    // 2023 day 24 solves its system exactly, so its integer answer comes from `to_integer`.
+    #[allow(dead_code)]
+    pub fn to_f64(self) -> f64 {
+        let [numerator, denominator] = [self.numerator, self.denominator].map(|value| {
+            value
+                .to_f64()
+                .expect("integer should be convertible to f64")
+        });
+        numerator / denominator
+    }
+}
+
+fn checked<T>(value: Option<T>) -> T {
+    value.expect("rational arithmetic should not overflow")
+}
+
+impl<T: PrimInt + Signed> From<T> for Rational<T> {
+    fn from(integer: T) -> Self {
+        Self {
+            numerator: integer,
+            denominator: T::one(),
+        }
+    }
+}
+
+impl<T: PrimInt + Signed> Add for Rational<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let divisor = gcd(self.denominator, rhs.denominator);
+        let [left_factor, right_factor] = [rhs.denominator / divisor, self.denominator / divisor];
+        let numerator = checked(
+            checked(self.numerator.checked_mul(&left_factor))
+                .checked_add(&checked(rhs.numerator.checked_mul(&right_factor))),
+        );
+        Self::new(
+            numerator,
+            checked(self.denominator.checked_mul(&left_factor)),
+        )
+    }
+}
+
+impl<T: PrimInt + Signed> Sub for Rational<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl<T: PrimInt + Signed> Mul for Rational<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let [left_divisor, right_divisor] = [
+            gcd(self.numerator, rhs.denominator),
+            gcd(rhs.numerator, self.denominator),
+        ];
+        let numerator =
+            (self.numerator / left_divisor).checked_mul(&(rhs.numerator / right_divisor));
+        let denominator =
+            (self.denominator / right_divisor).checked_mul(&(rhs.denominator / left_divisor));
+        Self::new(checked(numerator), checked(denominator))
+    }
+}
+
+impl<T: PrimInt + Signed> Div for Rational<T> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        assert!(!rhs.numerator.is_zero(), "divisor should not be zero");
+        self * Self::new(rhs.denominator, rhs.numerator)
+    }
+}
+
+impl<T: PrimInt + Signed> Neg for Rational<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            numerator: -self.numerator,
+            denominator: self.denominator,
+        }
+    }
+}
+
+impl<T: PrimInt + Signed> Zero for Rational<T> {
+    fn zero() -> Self {
+        Self::from(T::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.numerator.is_zero()
+    }
+}
+
+impl<T: PrimInt + Signed> One for Rational<T> {
+    fn one() -> Self {
+        Self::from(T::one())
+    }
+}
+
+impl<T: PrimInt + Signed> PartialOrd for Rational<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: PrimInt + Signed> Ord for Rational<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let left = checked(self.numerator.checked_mul(&other.denominator));
+        let right = checked(other.numerator.checked_mul(&self.denominator));
+        left.cmp(&right)
+    }
+}
+
+impl<T: Display + One + PartialEq> Display for Rational<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.denominator.is_one() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Fraction = Rational<i64>;
+
+    #[test]
+    fn normalization() {
+        assert_eq!(Fraction::new(6, -4), Fraction::new(-3, 2));
+        assert_eq!(Fraction::new(0, -5), Fraction::zero());
+        assert_eq!(Fraction::new(-3, 2).to_string(), "-3/2");
+        assert_eq!(Fraction::new(8, 4).to_integer(), Some(2));
+        assert_eq!(Fraction::new(7, 4).to_integer(), None);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let [half, third] = [Fraction::new(1, 2), Fraction::new(1, 3)];
+        assert_eq!(half + third, Fraction::new(5, 6));
+        assert_eq!(half - third, Fraction::new(1, 6));
+        assert_eq!(half * third, Fraction::new(1, 6));
+        assert_eq!(half / third, Fraction::new(3, 2));
+        assert_eq!(-half, Fraction::new(-1, 2));
+        assert!(third < half);
+        assert!(-half < third);
+    }
+
+    #[test]
+    fn large_values_stay_exact() {
+        let large = Rational::<i128>::new(10_i128.pow(30) + 1, 3);
+        let sum = (0..3).fold(Rational::zero(), |sum, _| sum + large);
+        assert_eq!(sum.to_integer(), Some(10_i128.pow(30) + 1));
+    }
+}
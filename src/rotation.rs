@@ -0,0 +1,135 @@
+pub mod cube_net;
+
+use std::{array, ops::Mul};
+
+use itertools::Itertools;
+
+pub use self::cube_net::CubeNet;
+use crate::{
+    grid::Coordinate,
+    vector::{CrossProduct, Vector},
+    HashSet,
+};
+
+pub type Vector3 = [Coordinate; 3];
+
+/// One of the 24 rotations that map the cube `[-1, 1]^3` onto itself, as an integer matrix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Rotation {
+    rows: [Vector3; 3],
+}
+
+impl Rotation {
+    pub const IDENTITY: Self = Self {
+        rows: [[1, 0, 0], [0, 1, 0], [0, 0, 1]],
+    };
+
+    /// Returns the rotation that maps the `i`'th unit vector onto `images[i]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the images are not a right-handed set of unit vectors along the axes.
+    pub fn from_images(images: [Vector3; 3]) -> Self {
+        let rotation = Self {
+            rows: array::from_fn(|row| images.map(|image| image[row])),
+        };
+        assert!(
+            rotation * rotation.inverse() == Self::IDENTITY
+                && images[0].cross(images[1]) == images[2],
+            "images {images:?} should be the columns of a rotation matrix"
+        );
+        rotation
+    }
+
+    /// Returns the rotation by a quarter turn about the given axis, counterclockwise when looking
+    /// against the axis.
+    pub fn quarter_turn(axis: usize) -> Self {
+        let [next, after_next] = [(axis + 1) % 3, (axis + 2) % 3];
+        let mut images = Self::IDENTITY.rows;
+        images[next] = Self::IDENTITY.rows[after_next];
+        images[after_next] = Self::IDENTITY.rows[next].map(|coordinate| -coordinate);
+        Self::from_images(images)
+    }
+
+    /// Returns all 24 rotations in ascending order, which are all products of quarter turns.
+    // Folding the cube net only ever reaches the rotations of the six faces it visits.
+    #[allow(dead_code)]
+    pub fn all() -> Vec<Self> {
+        let mut rotations = HashSet::from_iter([Self::IDENTITY]);
+        let mut frontier = vec![Self::IDENTITY];
+        while let Some(rotation) = frontier.pop() {
+            for axis in 0..3 {
+                let next = rotation * Self::quarter_turn(axis);
+                if rotations.insert(next) {
+                    frontier.push(next);
+                }
+            }
+        }
+        rotations.into_iter().sorted_unstable().collect()
+    }
+
+    pub fn apply(self, vector: Vector3) -> Vector3 {
+        self.rows.map(|row| row.dot(vector))
+    }
+
+    /// Returns the inverse rotation, which is the transpose because the matrix is orthogonal.
+    pub fn inverse(self) -> Self {
+        Self {
+            rows: array::from_fn(|row| self.rows.map(|other_row| other_row[row])),
+        }
+    }
+}
+
+/// Composes two rotations, where `left * right` applies `right` first.
+impl Mul for Rotation {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let columns = rhs.inverse().rows;
+        Self {
+            rows: self.rows.map(|row| columns.map(|column| row.dot(column))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quarter_turns() {
+        let about_z = Rotation::quarter_turn(2);
+        assert_eq!(about_z.apply([1, 0, 0]), [0, 1, 0]);
+        assert_eq!(about_z.apply([0, 1, 0]), [-1, 0, 0]);
+        assert_eq!(about_z.apply([0, 0, 1]), [0, 0, 1]);
+        let about_x = Rotation::quarter_turn(0);
+        assert_eq!(about_x.apply([0, 1, 0]), [0, 0, 1]);
+        let full_turn = (0..4).fold(Rotation::IDENTITY, |product, _| product * about_x);
+        assert_eq!(full_turn, Rotation::IDENTITY);
+        assert_eq!((about_x * about_z).apply([1, 0, 0]), [0, 0, 1]);
+    }
+
+    #[test]
+    fn group_of_rotations() {
+        let rotations = Rotation::all();
+        assert_eq!(rotations.len(), 24);
+        let rotation_set: HashSet<Rotation> = rotations.iter().copied().collect();
+        for &rotation in &rotations {
+            assert_eq!(rotation * rotation.inverse(), Rotation::IDENTITY);
+            assert_eq!(rotation.inverse() * rotation, Rotation::IDENTITY);
+            let [x, y, z] = Rotation::IDENTITY.rows.map(|unit| rotation.apply(unit));
+            assert_eq!(x.cross(y), z, "rotation should preserve handedness");
+            for &other in &rotations {
+                assert!(
+                    rotation_set.contains(&(rotation * other)),
+                    "rotations should be closed under composition"
+                );
+                let vector = [1, 2, 3];
+                assert_eq!(
+                    (rotation * other).apply(vector),
+                    rotation.apply(other.apply(vector))
+                );
+            }
+        }
+    }
+}
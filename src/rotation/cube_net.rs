@@ -0,0 +1,229 @@
+use easy_cast::Conv;
+use itertools::Itertools;
+
+use super::{Rotation, Vector3};
+use crate::{
+    grid::{self, Coordinate, Direction, Grid, Position},
+    number_theory::isqrt,
+    vector::{Negation, RotationInTwoDimensions, Vector},
+    HashMap,
+};
+
+/// The position of a face within the net, measured in faces rather than tiles.
+pub type Face = Position;
+
+const NORMAL: Vector3 = [0, 0, 1];
+
+/// A net of six square faces folded into a cube. Every face has an orientation that maps its local
+/// frame, the row and column directions of the net followed by the outward normal, onto the cube.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CubeNet {
+    face_size: Coordinate,
+    orientations: HashMap<Face, Rotation>,
+}
+
+impl CubeNet {
+    /// Folds the net whose faces consist of all tiles other than `' '`, or returns `None` if they
+    /// do not form one of the 11 nets of a cube.
+    pub fn fold(grid: &Grid<char>) -> Option<Self> {
+        let tiles = grid
+            .iter_row_major()
+            .filter(|(_, &tile)| tile != ' ')
+            .map(|(position, _)| position)
+            .collect_vec();
+        let face_size = isqrt(u128::conv(tiles.len() / 6));
+        let face_size = Coordinate::conv(face_size);
+        if face_size == 0 || usize::conv(6 * face_size * face_size) != tiles.len() {
+            return None;
+        }
+        let faces = tiles
+            .iter()
+            .map(|position| position.map(|coordinate| coordinate.div_euclid(face_size)))
+            .unique()
+            .collect_vec();
+        if faces.len() != 6 {
+            return None;
+        }
+
+        let mut orientations = HashMap::from_iter([(faces[0], Rotation::IDENTITY)]);
+        let mut frontier = vec![faces[0]];
+        while let Some(face) = frontier.pop() {
+            let orientation = orientations[&face];
+            for direction in grid::DIRECTIONS {
+                let neighbor = face.add(direction);
+                if faces.contains(&neighbor) && !orientations.contains_key(&neighbor) {
+                    orientations.insert(neighbor, orientation * roll(direction));
+                    frontier.push(neighbor);
+                }
+            }
+        }
+        let normals = orientations
+            .values()
+            .map(|orientation| orientation.apply(NORMAL))
+            .unique()
+            .count();
+        (normals == 6).then_some(Self {
+            face_size,
+            orientations,
+        })
+    }
+
+    // 2022 day 22 only steps across edges, so the face size stays internal to the net.
+    #[allow(dead_code)]
+    pub fn face_size(&self) -> Coordinate {
+        self.face_size
+    }
+
+    /// Returns the face that contains the tile at `position`, if any.
+    pub fn face(&self, position: Position) -> Option<Face> {
+        let face = position.map(|coordinate| coordinate.div_euclid(self.face_size));
+        self.orientations.contains_key(&face).then_some(face)
+    }
+
+    /// Returns the face across the edge of `face` in `direction`, together with the direction in
+    /// which that face is entered.
+    pub fn neighbor(&self, face: Face, direction: Direction) -> (Face, Direction) {
+        let orientation = self.orientations[&face];
+        let [row, column] = direction;
+        let neighbor_normal = orientation.apply([row, column, 0]);
+        let (&neighbor, neighbor_orientation) = self
+            .orientations
+            .iter()
+            .find(|(_, other)| other.apply(NORMAL) == neighbor_normal)
+            .expect("every edge of a folded cube should have a neighbor");
+        let [entry_row, entry_column, entry_normal] = neighbor_orientation
+            .inverse()
+            .apply(orientation.apply(NORMAL).neg());
+        debug_assert_eq!(entry_normal, 0, "entry should be along the face");
+        (neighbor, [entry_row, entry_column])
+    }
+
+    /// Returns the position and direction after leaving the face of `position` in `direction`,
+    /// around the edge of the cube.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `position` is not on a face.
+    pub fn wrap(&self, position: Position, direction: Direction) -> (Position, Direction) {
+        let face = self.face(position).expect("position should be on a face");
+        let (neighbor, entry_direction) = self.neighbor(face, direction);
+        // Relative to the center of the face and doubled to stay integer, the offset to the right
+        // of the direction of travel is the same on both sides of the edge.
+        let last = self.face_size - 1;
+        let centered = position
+            .sub(face.mul(self.face_size))
+            .mul(2)
+            .sub([last, last]);
+        let offset = centered.dot(direction.right());
+        let entry = entry_direction
+            .mul(-last)
+            .add(entry_direction.right().mul(offset))
+            .add([last, last])
+            .div(2);
+        (neighbor.mul(self.face_size).add(entry), entry_direction)
+    }
+}
+
+/// Returns the rotation of the frame of a face onto the frame of its neighbor in `direction`,
+/// which is folded such that its normal points in `direction` and `direction` becomes the
+/// opposite of the normal.
+fn roll([row, column]: Direction) -> Rotation {
+    let direction = [row, column, 0];
+    let images = Rotation::IDENTITY.rows.map(|unit| {
+        let along_direction = unit.dot(direction);
+        let along_normal = unit.dot(NORMAL);
+        unit.sub(direction.mul(along_direction))
+            .sub(NORMAL.mul(along_normal))
+            .add(NORMAL.neg().mul(along_direction))
+            .add(direction.mul(along_normal))
+    });
+    Rotation::from_images(images)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The 11 nets of a cube, with one character per face.
+    const NETS: [&str; 11] = [
+        "#   \n####\n#   \n",
+        "#   \n####\n #  \n",
+        "#   \n####\n  # \n",
+        "#   \n####\n   #\n",
+        " #  \n####\n #  \n",
+        " #  \n####\n  # \n",
+        "##  \n ###\n #  \n",
+        "##  \n ###\n  # \n",
+        "##  \n ###\n   #\n",
+        "##  \n ## \n  ##\n",
+        "### \n  ###\n",
+    ];
+
+    fn net(faces: &str, face_size: usize) -> Grid<char> {
+        let width = faces.lines().map(str::len).max().unwrap_or_default();
+        let mut rows = faces.lines().flat_map(|line| {
+            let row = format!("{line:width$}")
+                .chars()
+                .flat_map(|face| {
+                    itertools::repeat_n(if face == '#' { '.' } else { ' ' }, face_size)
+                })
+                .collect::<String>();
+            itertools::repeat_n(row, face_size)
+        });
+        Grid::from(rows.join("\n"))
+    }
+
+    #[test]
+    fn every_cube_net_folds() {
+        for faces in NETS {
+            let net = CubeNet::fold(&net(faces, 3)).expect("net should fold into a cube");
+            assert_eq!(net.face_size(), 3);
+            for &face in net.orientations.keys() {
+                for direction in grid::DIRECTIONS {
+                    let (neighbor, entry_direction) = net.neighbor(face, direction);
+                    assert_eq!(
+                        net.neighbor(neighbor, entry_direction.neg()),
+                        (face, direction.neg()),
+                        "edge from {face:?} toward {direction:?} should lead back in {faces:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn other_shapes_do_not_fold() {
+        for faces in ["######\n", "###\n###\n", "####\n#  #\n", "#  \n###\n#  \n"] {
+            assert_eq!(CubeNet::fold(&net(faces, 2)), None, "{faces:?}");
+        }
+    }
+
+    #[test]
+    fn walking_straight_around_the_cube_returns_to_start() {
+        let face_size = 3;
+        for faces in NETS {
+            let grid = net(faces, face_size);
+            let net = CubeNet::fold(&grid).expect("net should fold into a cube");
+            for ((start, _), start_direction) in grid
+                .iter_row_major()
+                .filter(|(_, &tile)| tile == '.')
+                .cartesian_product(grid::DIRECTIONS)
+            {
+                let [mut position, mut direction] = [start, start_direction];
+                for _ in 0..4 * face_size {
+                    let ahead = position.add(direction);
+                    (position, direction) = if grid.get(ahead) == Some(&'.') {
+                        (ahead, direction)
+                    } else {
+                        net.wrap(position, direction)
+                    };
+                }
+                assert_eq!(
+                    (position, direction),
+                    (start, start_direction),
+                    "walk in {faces:?} should return to start"
+                );
+            }
+        }
+    }
+}
@@ -1,11 +1,17 @@
+pub mod branch_and_bound;
+pub mod cycle;
+
 use std::{
-    cmp::Reverse,
+    cmp::{self, Ordering, Reverse},
     collections::{hash_map::Entry, BinaryHeap},
     hash::Hash,
-    mem,
+    iter, mem,
     ops::Add,
 };
 
+use easy_cast::Cast;
+use itertools::Itertools;
+
 use crate::{HashMap, HashSet};
 
 pub struct Exploration<T: Copy + Eq + Hash>(HashSet<T>);
@@ -61,6 +67,8 @@ where
     None
 }
 
+// 2023 day 25 finds its cut with `graph::flow` now, which left this without a caller.
+#[allow(dead_code)]
 pub fn shortest_path<State, Successors>(
     source: State,
     mut successors: impl FnMut(State) -> Successors,
@@ -82,7 +90,7 @@ where
                     Entry::Vacant(entry) => {
                         entry.insert(Some(state));
                         if target(successor) {
-                            return Some(path(&predecessors, successor));
+                            return Some(path(|state| predecessors[&state], successor));
                         }
                         next_ring.push(successor);
                     }
@@ -94,12 +102,12 @@ where
 }
 
 fn path<State: Copy + Eq + Hash>(
-    predecessors: &HashMap<State, Option<State>>,
+    mut predecessor: impl FnMut(State) -> Option<State>,
     end: State,
 ) -> Vec<State> {
     let mut current = end;
     let mut path = vec![end];
-    while let Some(predecessor) = predecessors[&current] {
+    while let Some(predecessor) = predecessor(current) {
         path.push(predecessor);
         current = predecessor;
     }
@@ -135,6 +143,8 @@ where
     distances
 }
 
+// 2023 day 17 moved to `astar_cost`, which left this plain Dijkstra search without a caller.
+#[allow(dead_code)]
 pub fn cheapest_path_cost<State, Cost, Successors>(
     source: State,
     mut successors: impl FnMut(State) -> Successors,
@@ -166,3 +176,488 @@ where
     }
     None
 }
+
+pub fn cheapest_path<State, Cost, Successors>(
+    source: State,
+    successors: impl FnMut(State) -> Successors,
+    target: impl FnMut(State) -> bool,
+) -> Option<(Cost, Vec<State>)>
+where
+    State: Copy + Eq + Hash + Ord,
+    Cost: Copy + Ord + Default + Add<Cost, Output = Cost>,
+    Successors: IntoIterator<Item = (State, Cost)>,
+{
+    astar_path(source, successors, |_| Cost::default(), target)
+}
+
+// No puzzle needs the cost of every reachable state yet, only of the targets.
+#[allow(dead_code)]
+pub fn cheapest_costs<State, Cost, Successors>(
+    source: State,
+    mut successors: impl FnMut(State) -> Successors,
+) -> HashMap<State, Cost>
+where
+    State: Copy + Eq + Hash + Ord,
+    Cost: Copy + Ord + Default + Add<Cost, Output = Cost>,
+    Successors: IntoIterator<Item = (State, Cost)>,
+{
+    let mut costs = HashMap::new();
+    let mut frontier: BinaryHeap<(Reverse<Cost>, State)> =
+        BinaryHeap::from([(Reverse(Cost::default()), source)]);
+    while let Some((Reverse(path_cost), state)) = frontier.pop() {
+        match costs.entry(state) {
+            Entry::Occupied(_) => (),
+            Entry::Vacant(entry) => {
+                entry.insert(path_cost);
+                for (successor, step_cost) in successors(state) {
+                    if !costs.contains_key(&successor) {
+                        frontier.push((Reverse(path_cost + step_cost), successor));
+                    }
+                }
+            }
+        }
+    }
+    costs
+}
+
+/// Returns every state on any cheapest path from `source` to a target, or an empty set if no
+/// target is reachable. Paths end at the first target they reach.
+pub fn states_on_cheapest_paths<State, Cost, Successors>(
+    source: State,
+    mut successors: impl FnMut(State) -> Successors,
+    mut target: impl FnMut(State) -> bool,
+) -> HashSet<State>
+where
+    State: Copy + Eq + Hash + Ord,
+    Cost: Copy + Ord + Default + Add<Cost, Output = Cost>,
+    Successors: IntoIterator<Item = (State, Cost)>,
+{
+    let mut best: HashMap<State, (Cost, Vec<State>)> =
+        HashMap::from([(source, (Cost::default(), vec![]))]);
+    let mut explored = HashSet::new();
+    let mut frontier: BinaryHeap<(Reverse<Cost>, State)> =
+        BinaryHeap::from([(Reverse(Cost::default()), source)]);
+    let mut targets = vec![];
+    let mut target_cost = None;
+    while let Some((Reverse(path_cost), state)) = frontier.pop() {
+        if target_cost.is_some_and(|target_cost| path_cost > target_cost) {
+            break;
+        }
+        if !explored.insert(state) {
+            continue;
+        }
+        if target(state) {
+            target_cost = Some(path_cost);
+            targets.push(state);
+            continue;
+        }
+        for (successor, step_cost) in successors(state) {
+            let successor_cost = path_cost + step_cost;
+            match best.entry(successor) {
+                Entry::Occupied(mut entry) => {
+                    let (cost, predecessors) = entry.get_mut();
+                    match successor_cost.cmp(cost) {
+                        Ordering::Less => {
+                            *cost = successor_cost;
+                            *predecessors = vec![state];
+                        }
+                        Ordering::Equal => {
+                            predecessors.push(state);
+                            continue;
+                        }
+                        Ordering::Greater => continue,
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert((successor_cost, vec![state]));
+                }
+            }
+            frontier.push((Reverse(successor_cost), successor));
+        }
+    }
+
+    let mut states = HashSet::new();
+    while let Some(state) = targets.pop() {
+        if states.insert(state) {
+            targets.extend(&best[&state].1);
+        }
+    }
+    states
+}
+
+pub fn astar_cost<State, Cost, Successors>(
+    source: State,
+    successors: impl FnMut(State) -> Successors,
+    heuristic: impl FnMut(State) -> Cost,
+    target: impl FnMut(State) -> bool,
+) -> Option<Cost>
+where
+    State: Copy + Eq + Hash + Ord,
+    Cost: Copy + Ord + Default + Add<Cost, Output = Cost>,
+    Successors: IntoIterator<Item = (State, Cost)>,
+{
+    astar(source, successors, heuristic, target).map(|(cost, _, _)| cost)
+}
+
+pub fn astar_path<State, Cost, Successors>(
+    source: State,
+    successors: impl FnMut(State) -> Successors,
+    heuristic: impl FnMut(State) -> Cost,
+    target: impl FnMut(State) -> bool,
+) -> Option<(Cost, Vec<State>)>
+where
+    State: Copy + Eq + Hash + Ord,
+    Cost: Copy + Ord + Default + Add<Cost, Output = Cost>,
+    Successors: IntoIterator<Item = (State, Cost)>,
+{
+    let (cost, end, best) = astar(source, successors, heuristic, target)?;
+    Some((cost, path(|state| best[&state].1, end)))
+}
+
+type Best<State, Cost> = HashMap<State, (Cost, Option<State>)>;
+
+fn astar<State, Cost, Successors>(
+    source: State,
+    mut successors: impl FnMut(State) -> Successors,
+    mut heuristic: impl FnMut(State) -> Cost,
+    mut target: impl FnMut(State) -> bool,
+) -> Option<(Cost, State, Best<State, Cost>)>
+where
+    State: Copy + Eq + Hash + Ord,
+    Cost: Copy + Ord + Default + Add<Cost, Output = Cost>,
+    Successors: IntoIterator<Item = (State, Cost)>,
+{
+    let mut best: Best<State, Cost> = HashMap::from([(source, (Cost::default(), None))]);
+    let mut frontier: BinaryHeap<(Reverse<Cost>, Cost, State)> =
+        BinaryHeap::from([(Reverse(heuristic(source)), Cost::default(), source)]);
+    while let Some((_, path_cost, state)) = frontier.pop() {
+        if best[&state].0 < path_cost {
+            continue;
+        }
+        if target(state) {
+            return Some((path_cost, state, best));
+        }
+        for (successor, step_cost) in successors(state) {
+            let successor_cost = path_cost + step_cost;
+            match best.entry(successor) {
+                Entry::Occupied(entry) if entry.get().0 <= successor_cost => continue,
+                Entry::Occupied(mut entry) => {
+                    entry.insert((successor_cost, Some(state)));
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert((successor_cost, Some(state)));
+                }
+            }
+            let estimate = successor_cost + heuristic(successor);
+            frontier.push((Reverse(estimate), successor_cost, successor));
+        }
+    }
+    None
+}
+
+/// Finds the length of a longest path from `from` to `to` that visits no node twice, or `None` if
+/// `to` is unreachable. The graph should have at most 128 nodes.
+pub fn longest_simple_path<Node: Copy + Eq + Hash>(
+    graph: &HashMap<Node, Vec<(Node, usize)>>,
+    from: Node,
+    to: Node,
+) -> Option<usize> {
+    let nodes = graph
+        .iter()
+        .flat_map(|(&node, edges)| iter::once(node).chain(edges.iter().map(|&(to, _)| to)))
+        .chain([from, to])
+        .unique()
+        .collect_vec();
+    assert!(
+        nodes.len() <= LongestSimplePath::MAXIMUM_NUMBER_OF_NODES,
+        "graph should have at most 128 nodes"
+    );
+    let indices: HashMap<Node, usize> = nodes
+        .iter()
+        .enumerate()
+        .map(|(index, &node)| (node, index))
+        .collect();
+    let edges = nodes
+        .iter()
+        .map(|node| {
+            graph.get(node).map_or(vec![], |edges| {
+                edges
+                    .iter()
+                    .map(|&(to, length)| (indices[&to], length))
+                    .collect_vec()
+            })
+        })
+        .collect_vec();
+    let mut longest_incoming = vec![0; nodes.len()];
+    let mut successors = vec![0; nodes.len()];
+    for (from, edges) in edges.iter().enumerate() {
+        for &(to, length) in edges {
+            longest_incoming[to] = cmp::max(longest_incoming[to], length);
+            successors[from] |= 1 << to;
+        }
+    }
+
+    let mut search = LongestSimplePath {
+        edges,
+        successors,
+        longest_incoming,
+        target: indices[&to],
+        memo: HashMap::new(),
+        longest: None,
+    };
+    search.explore(indices[&from], 1 << indices[&from], 0);
+    search.longest
+}
+
+type NodeSet = u128;
+
+struct LongestSimplePath {
+    edges: Vec<Vec<(usize, usize)>>,
+    successors: Vec<NodeSet>,
+    longest_incoming: Vec<usize>,
+    target: usize,
+    memo: HashMap<(usize, NodeSet), Option<usize>>,
+    longest: Option<usize>,
+}
+
+impl LongestSimplePath {
+    const MAXIMUM_NUMBER_OF_NODES: usize = NodeSet::BITS as usize;
+
+    /// Returns the longest remaining length from `node` to the target that avoids `visited`, and
+    /// whether that length is exact. The remaining path can only use unvisited nodes reachable
+    /// from `node`, so those nodes determine the result and serve as memoisation key. A branch is
+    /// cut, making the result inexact, if it cannot beat the longest path found so far even if it
+    /// entered every reachable node through its longest incoming edge.
+    fn explore(&mut self, node: usize, visited: NodeSet, length: usize) -> (Option<usize>, bool) {
+        if node == self.target {
+            self.longest = cmp::max(self.longest, Some(length));
+            return (Some(0), true);
+        }
+        let reachable = self.reachable(node, visited);
+        if reachable & (1 << self.target) == 0 {
+            return (None, true);
+        }
+        if let Some(&remaining) = self.memo.get(&(node, reachable)) {
+            self.longest = cmp::max(self.longest, remaining.map(|remaining| length + remaining));
+            return (remaining, true);
+        }
+        let bound: usize = nodes(reachable)
+            .map(|reachable| self.longest_incoming[reachable])
+            .sum();
+        if self
+            .longest
+            .is_some_and(|longest| length + bound <= longest)
+        {
+            return (None, false);
+        }
+
+        let mut longest_remaining = None;
+        let mut is_exact = true;
+        for index in 0..self.edges[node].len() {
+            let (next, edge_length) = self.edges[node][index];
+            if visited & (1 << next) != 0 {
+                continue;
+            }
+            let (remaining, is_remaining_exact) =
+                self.explore(next, visited | (1 << next), length + edge_length);
+            is_exact &= is_remaining_exact;
+            longest_remaining = cmp::max(
+                longest_remaining,
+                remaining.map(|remaining| edge_length + remaining),
+            );
+        }
+        if is_exact {
+            self.memo.insert((node, reachable), longest_remaining);
+        }
+        (longest_remaining, is_exact)
+    }
+
+    fn reachable(&self, node: usize, visited: NodeSet) -> NodeSet {
+        let mut reachable = self.successors[node] & !visited;
+        let mut frontier = reachable;
+        while frontier != 0 {
+            let next = nodes(frontier).fold(0, |next, node| next | self.successors[node]);
+            frontier = next & !visited & !reachable;
+            reachable |= frontier;
+        }
+        reachable
+    }
+}
+
+fn nodes(set: NodeSet) -> impl Iterator<Item = usize> {
+    let mut set = set;
+    iter::from_fn(move || {
+        (set != 0).then(|| {
+            let node = set.trailing_zeros();
+            set &= set - 1;
+            node.cast()
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    use super::*;
+    use crate::grid::{self, Grid, Position};
+
+    fn random_grid(rng: &mut SmallRng) -> Grid<usize> {
+        let [height, width] = [rng.gen_range(1..=12), rng.gen_range(1..=12)];
+        Grid::new(height, width, |_| {
+            if rng.gen_bool(0.2) {
+                usize::MAX
+            } else {
+                rng.gen_range(1..=9)
+            }
+        })
+    }
+
+    fn successors(grid: &Grid<usize>, position: Position) -> Vec<(Position, usize)> {
+        grid::neighbors(position)
+            .into_iter()
+            .filter_map(|neighbor| {
+                let &cost = grid.get(neighbor)?;
+                (cost != usize::MAX).then_some((neighbor, cost))
+            })
+            .collect()
+    }
+
+    fn predecessors(grid: &Grid<usize>, position: Position) -> Vec<(Position, usize)> {
+        let cost = grid[position];
+        grid::neighbors(position)
+            .into_iter()
+            .filter(|&neighbor| cost != usize::MAX && grid.get(neighbor).is_some())
+            .map(|neighbor| (neighbor, cost))
+            .collect()
+    }
+
+    #[test]
+    fn astar_matches_dijkstra_on_random_grids() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..500 {
+            let grid = random_grid(&mut rng);
+            let [target_row, target_column] = grid.corners_clockwise()[2];
+            let target = |position| position == [target_row, target_column];
+            let manhattan = |[row, column]: Position| {
+                (target_row - row).unsigned_abs() + (target_column - column).unsigned_abs()
+            };
+            let dijkstra =
+                cheapest_path_cost([0, 0], |position| successors(&grid, position), target);
+            let astar = astar_cost(
+                [0, 0],
+                |position| successors(&grid, position),
+                manhattan,
+                target,
+            );
+            assert_eq!(astar, dijkstra, "costs should match on grid {grid:?}");
+
+            let path = astar_path(
+                [0, 0],
+                |position| successors(&grid, position),
+                manhattan,
+                target,
+            );
+            assert_eq!(path.as_ref().map(|&(cost, _)| cost), dijkstra);
+            if let Some((cost, path)) = path {
+                assert_eq!(path.first(), Some(&[0, 0]), "path should start at source");
+                assert_eq!(
+                    path.last(),
+                    Some(&[target_row, target_column]),
+                    "path should end at target"
+                );
+                let path_cost: usize = path[1..].iter().map(|&position| grid[position]).sum();
+                assert_eq!(path_cost, cost, "path cost should match returned cost");
+            }
+        }
+    }
+
+    #[test]
+    fn dijkstra_variants_agree_on_random_grids() {
+        let mut rng = SmallRng::seed_from_u64(1);
+        for _ in 0..500 {
+            let grid = random_grid(&mut rng);
+            let target = grid.corners_clockwise()[2];
+            let is_target = |position| position == target;
+            let cost =
+                cheapest_path_cost([0, 0], |position| successors(&grid, position), is_target);
+
+            let path = cheapest_path([0, 0], |position| successors(&grid, position), is_target);
+            assert_eq!(path.as_ref().map(|&(cost, _)| cost), cost);
+            let costs = cheapest_costs([0, 0], |position| successors(&grid, position));
+            assert_eq!(costs.get(&target).copied(), cost);
+
+            let states =
+                states_on_cheapest_paths([0, 0], |position| successors(&grid, position), is_target);
+            let Some(cost) = cost else {
+                assert!(states.is_empty(), "unreachable target should have no paths");
+                continue;
+            };
+            let costs_to_target = cheapest_costs(target, |position| predecessors(&grid, position));
+            let expected: HashSet<Position> = costs
+                .iter()
+                .filter(|&(position, cost_from_source)| {
+                    costs_to_target
+                        .get(position)
+                        .map(|cost_to_target| cost_from_source + cost_to_target)
+                        == Some(cost)
+                })
+                .map(|(&position, _)| position)
+                .collect();
+            assert_eq!(
+                states, expected,
+                "states should lie on cheapest paths in {grid:?}"
+            );
+            let (_, path) = path.expect("path should exist when cost exists");
+            assert!(
+                path.iter().all(|position| states.contains(position)),
+                "cheapest path should only visit states on cheapest paths"
+            );
+        }
+    }
+
+    fn longest_by_enumeration(
+        graph: &HashMap<usize, Vec<(usize, usize)>>,
+        path: &mut Vec<usize>,
+        to: usize,
+    ) -> Option<usize> {
+        let &node = path.last().expect("path should not be empty");
+        if node == to {
+            return Some(0);
+        }
+        let mut longest = None;
+        for &(next, length) in graph.get(&node).into_iter().flatten() {
+            if !path.contains(&next) {
+                path.push(next);
+                let remaining = longest_by_enumeration(graph, path, to);
+                longest = longest.max(remaining.map(|remaining| length + remaining));
+                path.pop();
+            }
+        }
+        longest
+    }
+
+    #[test]
+    fn longest_simple_path_matches_enumeration() {
+        let mut rng = SmallRng::seed_from_u64(2);
+        for _ in 0..300 {
+            let number_of_nodes = rng.gen_range(1..=9);
+            let mut graph: HashMap<usize, Vec<(usize, usize)>> = HashMap::new();
+            for _ in 0..rng.gen_range(0..=20) {
+                let [from, to] = [0, 1].map(|_| rng.gen_range(0..number_of_nodes));
+                let length = rng.gen_range(1..=10);
+                graph.entry(from).or_default().push((to, length));
+                if rng.gen_bool(0.5) {
+                    graph.entry(to).or_default().push((from, length));
+                }
+            }
+            let to = number_of_nodes - 1;
+            let expected = longest_by_enumeration(&graph, &mut vec![0], to);
+            assert_eq!(
+                longest_simple_path(&graph, 0, to),
+                expected,
+                "longest path in {graph:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use std::{collections::BinaryHeap, hash::Hash};
+
+use crate::HashSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Traversal {
+    DepthFirst,
+    BestFirst,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Options {
+    pub traversal: Traversal,
+    pub memoise: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Statistics {
+    pub expanded: usize,
+    pub pruned: usize,
+    pub duplicates: usize,
+}
+
+/// Finds the largest objective value among `initial` and all states reachable from it. States
+/// whose optimistic `bound` on the objective of themselves and all their descendants cannot beat
+/// the best value found so far are not expanded. With memoisation, every state is only considered
+/// once.
+pub fn maximize<State, Value, Successors>(
+    initial: State,
+    mut successors: impl FnMut(&State) -> Successors,
+    mut objective: impl FnMut(&State) -> Value,
+    mut bound: impl FnMut(&State) -> Value,
+    options: Options,
+) -> (Value, Statistics)
+where
+    State: Clone + Eq + Hash,
+    Value: Copy + Ord,
+    Successors: IntoIterator<Item = State>,
+{
+    let mut statistics = Statistics::default();
+    let mut best = objective(&initial);
+    let mut seen = HashSet::new();
+    if options.memoise {
+        seen.insert(initial.clone());
+    }
+    let mut frontier = Frontier::new(options.traversal);
+    frontier.push(bound(&initial), initial);
+    while let Some((state_bound, state)) = frontier.pop() {
+        if state_bound <= best {
+            statistics.pruned += 1;
+            continue;
+        }
+        statistics.expanded += 1;
+        for successor in successors(&state) {
+            if options.memoise && !seen.insert(successor.clone()) {
+                statistics.duplicates += 1;
+                continue;
+            }
+            best = best.max(objective(&successor));
+            let successor_bound = bound(&successor);
+            if successor_bound > best {
+                frontier.push(successor_bound, successor);
+            } else {
+                statistics.pruned += 1;
+            }
+        }
+    }
+    (best, statistics)
+}
+
+enum Frontier<State, Value> {
+    Stack(Vec<(Value, State)>),
+    Heap(BinaryHeap<(Value, usize)>, Vec<Option<State>>),
+}
+
+impl<State, Value: Ord> Frontier<State, Value> {
+    fn new(traversal: Traversal) -> Self {
+        match traversal {
+            Traversal::DepthFirst => Frontier::Stack(vec![]),
+            Traversal::BestFirst => Frontier::Heap(BinaryHeap::new(), vec![]),
+        }
+    }
+
+    fn push(&mut self, bound: Value, state: State) {
+        match self {
+            Frontier::Stack(stack) => stack.push((bound, state)),
+            Frontier::Heap(heap, states) => {
+                heap.push((bound, states.len()));
+                states.push(Some(state));
+            }
+        }
+    }
+
+    fn pop(&mut self) -> Option<(Value, State)> {
+        match self {
+            Frontier::Stack(stack) => stack.pop(),
+            Frontier::Heap(heap, states) => {
+                let (bound, index) = heap.pop()?;
+                let state = states[index]
+                    .take()
+                    .expect("state should only be popped once");
+                Some((bound, state))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    use super::*;
+
+    type Item = (usize, usize);
+
+    /// Knapsack states are the index of the next item to decide on, the remaining capacity and
+    /// the value packed so far.
+    fn knapsack(items: &[Item], capacity: usize, options: Options) -> (usize, Statistics) {
+        let successors = |&(index, capacity, value): &(usize, usize, usize)| {
+            let Some(&(weight, item_value)) = items.get(index) else {
+                return vec![];
+            };
+            let mut successors = vec![(index + 1, capacity, value)];
+            if weight <= capacity {
+                successors.push((index + 1, capacity - weight, value + item_value));
+            }
+            successors
+        };
+        let bound = |&(index, capacity, value): &(usize, usize, usize)| {
+            value
+                + items[index..]
+                    .iter()
+                    .filter(|&&(weight, _)| weight <= capacity)
+                    .map(|&(_, value)| value)
+                    .sum::<usize>()
+        };
+        maximize(
+            (0, capacity, 0),
+            successors,
+            |&(_, _, value)| value,
+            bound,
+            options,
+        )
+    }
+
+    fn brute_force(items: &[Item], capacity: usize) -> usize {
+        items
+            .iter()
+            .powerset()
+            .filter(|subset| subset.iter().map(|&&(weight, _)| weight).sum::<usize>() <= capacity)
+            .map(|subset| subset.iter().map(|&&(_, value)| value).sum())
+            .max()
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn knapsack_matches_brute_force() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let items = (0..rng.gen_range(0..=10))
+                .map(|_| (rng.gen_range(1..=10), rng.gen_range(1..=10)))
+                .collect_vec();
+            let capacity = rng.gen_range(0..=30);
+            let expected = brute_force(&items, capacity);
+            for traversal in [Traversal::DepthFirst, Traversal::BestFirst] {
+                for memoise in [false, true] {
+                    let options = Options { traversal, memoise };
+                    let (actual, _) = knapsack(&items, capacity, options);
+                    assert_eq!(
+                        actual, expected,
+                        "best value with {options:?} for {items:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn best_first_expands_fewer_states() {
+        let items = [(5, 10), (4, 40), (6, 30), (3, 50), (2, 5), (7, 25), (1, 1)];
+        let [depth_first, best_first] =
+            [Traversal::DepthFirst, Traversal::BestFirst].map(|traversal| {
+                knapsack(
+                    &items,
+                    10,
+                    Options {
+                        traversal,
+                        memoise: false,
+                    },
+                )
+            });
+        assert_eq!(depth_first.0, 96);
+        assert_eq!(best_first.0, 96);
+        assert!(
+            best_first.1.expanded <= depth_first.1.expanded,
+            "best-first search should expand at most as many states as depth-first search"
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use std::{
+    hash::Hash,
+    ops::{Add, Mul, Sub},
+};
+
+use crate::HashMap;
+
+/// The number of steps before a sequence of states enters its cycle, and the length of the cycle.
+pub type Cycle = (usize, usize);
+
+pub struct Detector<Key: Eq + Hash> {
+    first_occurrences: HashMap<Key, usize>,
+}
+
+impl<Key: Eq + Hash> Default for Detector<Key> {
+    fn default() -> Self {
+        Self {
+            first_occurrences: HashMap::new(),
+        }
+    }
+}
+
+impl<Key: Eq + Hash> Detector<Key> {
+    /// Records the key of the next state in the sequence and returns the cycle as soon as a key
+    /// repeats.
+    pub fn observe(&mut self, key: Key) -> Option<Cycle> {
+        let index = self.first_occurrences.len();
+        let &mut start = self.first_occurrences.entry(key).or_insert(index);
+        (start != index).then_some((start, index - start))
+    }
+}
+
+// The puzzles so far can afford to remember their states, so only `Detector` has callers.
+#[allow(dead_code)]
+pub fn floyd<State: Clone + Eq>(initial: State, mut step: impl FnMut(State) -> State) -> Cycle {
+    let mut tortoise = step(initial.clone());
+    let mut hare = step(tortoise.clone());
+    while tortoise != hare {
+        tortoise = step(tortoise);
+        hare = step(hare);
+        hare = step(hare);
+    }
+
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(tortoise);
+        hare = step(hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(tortoise.clone());
+    while tortoise != hare {
+        hare = step(hare);
+        length += 1;
+    }
+    (start, length)
+}
+
+// Like `floyd`, this saves memory at the cost of extra steps, which no puzzle needs yet.
+#[allow(dead_code)]
+pub fn brent<State: Clone + Eq>(initial: State, mut step: impl FnMut(State) -> State) -> Cycle {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(initial.clone());
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(hare);
+        length += 1;
+    }
+
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..length {
+        hare = step(hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(tortoise);
+        hare = step(hare);
+        start += 1;
+    }
+    (start, length)
+}
+
+// 2022 day 17 and 2023 days 8 and 14 observe keys while they step, through `Detector`.
+#[allow(dead_code)]
+pub fn keyed<State, Key: Eq + Hash>(
+    mut state: State,
+    mut step: impl FnMut(State) -> State,
+    mut key: impl FnMut(&State) -> Key,
+) -> Cycle {
+    let mut detector = Detector::default();
+    loop {
+        if let Some(cycle) = detector.observe(key(&state)) {
+            return cycle;
+        }
+        state = step(state);
+    }
+}
+
+pub fn state_after<State: Clone, Key: Eq + Hash>(
+    initial: State,
+    mut step: impl FnMut(State) -> State,
+    mut key: impl FnMut(&State) -> Key,
+    number_of_steps: usize,
+) -> State {
+    let mut detector = Detector::default();
+    let mut states = vec![initial];
+    loop {
+        let state = states.last().expect("there should be at least one state");
+        if states.len() > number_of_steps {
+            return state.clone();
+        }
+        if let Some((start, length)) = detector.observe(key(state)) {
+            return states.swap_remove(start + (number_of_steps - start) % length);
+        }
+        let next = step(state.clone());
+        states.push(next);
+    }
+}
+
+/// Extrapolates a value accumulated over the steps of a cycling sequence, given the accumulated
+/// values after every step up to and including the end of the first repetition of the cycle.
+pub fn extrapolate<Value>(values: &[Value], (start, length): Cycle, number_of_steps: usize) -> Value
+where
+    Value: Copy + Add<Output = Value> + Sub<Output = Value> + Mul<Output = Value> + From<usize>,
+{
+    if let Some(&value) = values.get(number_of_steps) {
+        return value;
+    }
+    let number_of_cycles = (number_of_steps - start) / length;
+    let remainder = (number_of_steps - start) % length;
+    let cycle_increase = values[start + length] - values[start];
+    values[start + remainder] + Value::from(number_of_cycles) * cycle_increase
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+    use super::*;
+
+    fn pseudorandom_step(modulus: usize, increment: usize) -> impl Fn(usize) -> usize {
+        move |state| (state * state + increment) % modulus
+    }
+
+    #[test]
+    fn detectors_agree() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..200 {
+            let modulus = rng.gen_range(1..1000);
+            let step = pseudorandom_step(modulus, rng.gen_range(0..modulus));
+            let initial = rng.gen_range(0..modulus);
+            let expected = keyed(initial, &step, |&state| state);
+            assert_eq!(
+                floyd(initial, &step),
+                expected,
+                "Floyd should match hashing"
+            );
+            assert_eq!(
+                brent(initial, &step),
+                expected,
+                "Brent should match hashing"
+            );
+        }
+    }
+
+    #[test]
+    fn rho_shaped_sequence() {
+        let step = |state: usize| if state < 5 { state + 1 } else { 2 };
+        assert_eq!(keyed(0, step, |&state| state), (2, 4));
+        assert_eq!(floyd(0, step), (2, 4));
+        assert_eq!(brent(0, step), (2, 4));
+        assert_eq!(keyed(3, step, |&state| state), (0, 4));
+    }
+
+    #[test]
+    fn state_after_many_steps() {
+        let step = pseudorandom_step(997, 3);
+        for number_of_steps in [0, 1, 10, 100, 1000] {
+            let expected = (0..number_of_steps).fold(2, |state, _| step(state));
+            let actual = state_after(2, &step, |&state| state, number_of_steps);
+            assert_eq!(actual, expected, "state after {number_of_steps} steps");
+        }
+        assert_eq!(state_after(2, &step, |&state| state, 1_000_000_000), {
+            let (start, length) = keyed(2, &step, |&state| state);
+            let remaining = start + (1_000_000_000 - start) % length;
+            (0..remaining).fold(2, |state, _| step(state))
+        });
+    }
+
+    #[test]
+    fn extrapolated_sum() {
+        let step = pseudorandom_step(101, 7);
+        let mut state = 5;
+        let mut sums = vec![0];
+        let mut detector = Detector::default();
+        let cycle = loop {
+            if let Some(cycle) = detector.observe(state) {
+                break cycle;
+            }
+            sums.push(sums.last().expect("sums should not be empty") + state);
+            state = step(state);
+        };
+        let mut state = 5;
+        let mut sum = 0;
+        for number_of_steps in 0..1000 {
+            assert_eq!(extrapolate(&sums, cycle, number_of_steps), sum);
+            sum += state;
+            state = step(state);
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use std::{
+    fs,
+    io::{self, BufRead, Write},
+    path::Path,
+    sync::mpsc::{self, Receiver},
+    thread,
+    time::Duration,
+};
+
+use anyhow::Result;
+use itertools::Itertools;
+
+use crate::{
+    grid::Grid,
+    image::{self, Color, Image},
+};
+
+pub trait Simulation {
+    fn frame(&self) -> Grid<char>;
+
+    /// Advances the simulation by one step and returns `false` once the simulation has finished.
+    fn step(&mut self) -> bool;
+
+    fn color(&self, element: char) -> Color {
+        match element {
+            '.' | ' ' => image::BLACK,
+            '#' | '|' | '-' | '+' => image::GRAY,
+            _ => image::YELLOW,
+        }
+    }
+}
+
+const MINIMUM_FRAMES_PER_SECOND: u16 = 1;
+const MAXIMUM_FRAMES_PER_SECOND: u16 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Command {
+    Pause,
+    Step,
+    Faster,
+    Slower,
+    Quit,
+}
+
+pub fn play(simulation: &mut dyn Simulation, mut frames_per_second: u16) {
+    let commands = commands();
+    let mut is_paused = false;
+    let mut frame_number = 0;
+    loop {
+        draw(
+            &simulation.frame(),
+            frame_number,
+            frames_per_second,
+            is_paused,
+        );
+        let command = if is_paused {
+            commands.recv().ok()
+        } else {
+            let delay = Duration::from_secs(1) / u32::from(frames_per_second);
+            commands.recv_timeout(delay).ok()
+        };
+        let advance = match command {
+            None => true,
+            Some(Command::Pause) => {
+                is_paused = !is_paused;
+                false
+            }
+            Some(Command::Step) => {
+                is_paused = true;
+                true
+            }
+            Some(Command::Faster) => {
+                frames_per_second = (frames_per_second * 2).min(MAXIMUM_FRAMES_PER_SECOND);
+                false
+            }
+            Some(Command::Slower) => {
+                frames_per_second = (frames_per_second / 2).max(MINIMUM_FRAMES_PER_SECOND);
+                false
+            }
+            Some(Command::Quit) => return,
+        };
+        if advance {
+            if !simulation.step() {
+                draw(
+                    &simulation.frame(),
+                    frame_number + 1,
+                    frames_per_second,
+                    true,
+                );
+                return;
+            }
+            frame_number += 1;
+        }
+    }
+}
+
+fn commands() -> Receiver<Command> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else {
+                return;
+            };
+            let command = match line.trim() {
+                "" | "p" => Command::Pause,
+                "s" => Command::Step,
+                "+" => Command::Faster,
+                "-" => Command::Slower,
+                "q" => Command::Quit,
+                _ => continue,
+            };
+            if sender.send(command).is_err() {
+                return;
+            }
+        }
+    });
+    receiver
+}
+
+fn draw(frame: &Grid<char>, frame_number: usize, frames_per_second: u16, is_paused: bool) {
+    let state = if is_paused { "paused" } else { "playing" };
+    let mut stdout = io::stdout().lock();
+    let drawn = write!(
+        stdout,
+        "\x1b[2J\x1b[H{frame}\nframe {frame_number}, {frames_per_second} fps, {state} \
+        (enter/p: pause, s: step, +/-: speed, q: quit)\n"
+    )
+    .and_then(|()| stdout.flush());
+    drawn.expect("frame should be written to terminal");
+}
+
+pub fn record(
+    simulation: &mut dyn Simulation,
+    path: impl AsRef<Path>,
+    maximum_number_of_frames: usize,
+    frames_per_second: u16,
+) -> Result<()> {
+    let mut frames = vec![simulation.frame()];
+    while frames.len() < maximum_number_of_frames && simulation.step() {
+        frames.push(simulation.frame());
+    }
+
+    let height = frames.iter().map(Grid::height).max().unwrap_or_default();
+    let width = frames.iter().map(Grid::width).max().unwrap_or_default();
+    let images = frames
+        .into_iter()
+        .map(|frame| {
+            let padded = Grid::new(height, width, |position| {
+                frame.get(position).copied().unwrap_or(' ')
+            });
+            Image::new(&padded, |_, &element| simulation.color(element))
+        })
+        .collect_vec();
+    fs::write(path, Image::animated_png(&images, frames_per_second))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Counter(usize);
+
+    impl Simulation for Counter {
+        fn frame(&self) -> Grid<char> {
+            Grid::new(1, self.0, |_| '#')
+        }
+
+        fn step(&mut self) -> bool {
+            self.0 += 1;
+            self.0 < 3
+        }
+    }
+
+    #[test]
+    fn record_stops_when_simulation_finishes() {
+        let file_name = format!(
+            "advent-of-code-record-stops-when-simulation-finishes-{}.png",
+            std::process::id()
+        );
+        let path = std::env::temp_dir().join(file_name);
+        record(&mut Counter(1), &path, 10, 5).expect("animation should be recorded");
+        let png = fs::read(&path).expect("animation should be readable");
+        fs::remove_file(&path).expect("animation should be removable");
+
+        let animation_control = png
+            .windows(4)
+            .position(|window| window == b"acTL")
+            .expect("animation should have an animation control chunk");
+        let number_of_frames = &png[animation_control + 4..animation_control + 8];
+        assert_eq!(number_of_frames, 2_u32.to_be_bytes());
+        let number_of_frame_controls = png.windows(4).filter(|&window| window == b"fcTL").count();
+        assert_eq!(number_of_frame_controls, 2);
+    }
+}
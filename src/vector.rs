@@ -1,6 +1,8 @@
-use std::{f64, ops::Neg};
+use std::ops::Neg;
 
-use num_traits::{NumCast, NumOps, Zero};
+use num_traits::{NumCast, NumOps, PrimInt, Signed, Zero};
+
+use crate::number_theory::isqrt;
 
 pub trait Vector {
     type Scalar;
@@ -9,8 +11,40 @@ pub trait Vector {
     fn mul(self, rhs: Self::Scalar) -> Self;
     fn div(self, rhs: Self::Scalar) -> Self;
     fn dot(self, rhs: Self) -> Self::Scalar;
+    /// Returns the Euclidean norm, computed in `f64` and converted back, which truncates for
+    /// integers.
     fn norm(self) -> Self::Scalar;
+    /// Returns the Euclidean norm if it is an integer.
+    // No puzzle has needed Euclidean lengths so far.
+    #[allow(dead_code)]
+    fn exact_norm(self) -> Option<Self::Scalar>
+    where
+        Self::Scalar: PrimInt;
+    fn manhattan(self) -> Self::Scalar
+    where
+        Self::Scalar: Signed;
+    fn chebyshev(self) -> Self::Scalar
+    where
+        Self::Scalar: Signed + Ord;
+    // Directions on grids are normalised with `signum`, which also handles diagonals.
+    #[allow(dead_code)]
     fn unit(self) -> Self;
+    fn signum(self) -> Self
+    where
+        Self::Scalar: Signed;
+    fn abs(self) -> Self
+    where
+        Self::Scalar: Signed;
+    // 2022 day 23 finds the bounding box of its elves per axis with `minmax`.
+    #[allow(dead_code)]
+    fn componentwise_min(self, rhs: Self) -> Self
+    where
+        Self::Scalar: Ord;
+    // The counterpart of `componentwise_min`, also without a caller.
+    #[allow(dead_code)]
+    fn componentwise_max(self, rhs: Self) -> Self
+    where
+        Self::Scalar: Ord;
 }
 
 impl<T, const N: usize> Vector for [T; N]
@@ -61,9 +95,68 @@ where
         T::from(dot.sqrt()).expect("norm should convert back to original number type")
     }
 
+    fn exact_norm(self) -> Option<T>
+    where
+        T: PrimInt,
+    {
+        let square = self.dot(self).to_u128()?;
+        let root = isqrt(square);
+        (root * root == square).then(|| T::from(root)).flatten()
+    }
+
+    fn manhattan(self) -> T
+    where
+        T: Signed,
+    {
+        self.abs()
+            .into_iter()
+            .fold(T::zero(), |sum, element| sum + element)
+    }
+
+    fn chebyshev(self) -> T
+    where
+        T: Signed + Ord,
+    {
+        self.abs().into_iter().max().unwrap_or_else(T::zero)
+    }
+
     fn unit(self) -> Self {
         self.div(self.norm())
     }
+
+    fn signum(self) -> Self
+    where
+        T: Signed,
+    {
+        self.map(|element| element.signum())
+    }
+
+    fn abs(self) -> Self
+    where
+        T: Signed,
+    {
+        self.map(|element| element.abs())
+    }
+
+    fn componentwise_min(mut self, rhs: Self) -> Self
+    where
+        T: Ord,
+    {
+        for (element, other) in self.iter_mut().zip(rhs) {
+            *element = (*element).min(other);
+        }
+        self
+    }
+
+    fn componentwise_max(mut self, rhs: Self) -> Self
+    where
+        T: Ord,
+    {
+        for (element, other) in self.iter_mut().zip(rhs) {
+            *element = (*element).max(other);
+        }
+        self
+    }
 }
 
 pub trait Negation {
@@ -116,28 +209,8 @@ where
     }
 }
 
-pub trait AngleInTwoDimensions {
-    fn angle(self, to: Self) -> f64;
-}
-
-impl<T> AngleInTwoDimensions for [T; 2]
-where
-    T: Copy + Zero + NumOps + NumCast,
-{
-    fn angle(self, to: Self) -> f64 {
-        let dot = <f64 as NumCast>::from(self.dot(to)).expect("number should convert to 'f64'");
-        let determinant = <f64 as NumCast>::from(self[0] * to[1] - self[1] * to[0])
-            .expect("number should convert to 'f64'");
-        determinant.atan2(dot)
-    }
-}
-
 #[cfg(test)]
 mod tests {
-    use std::f64::consts::*;
-
-    use crate::floating_point::ApproxEq;
-
     use super::*;
 
     #[test]
@@ -182,6 +255,25 @@ mod tests {
         assert_eq!(actual, 910);
     }
 
+    #[test]
+    fn exact_norm() {
+        assert_eq!([3, 4].exact_norm(), Some(5));
+        assert_eq!([2, 3, 6].exact_norm(), Some(7));
+        assert_eq!([1, 1].exact_norm(), None);
+        assert_eq!([0_u8; 3].exact_norm(), Some(0));
+        assert_eq!(
+            [300_000_000_i64, 400_000_000].exact_norm(),
+            Some(500_000_000)
+        );
+    }
+
+    #[test]
+    fn manhattan_and_chebyshev_norms() {
+        assert_eq!([3, -4, 0].manhattan(), 7);
+        assert_eq!([3, -4, 0].chebyshev(), 4);
+        assert_eq!([0; 2].chebyshev(), 0);
+    }
+
     #[test]
     fn unit_vector() {
         let actual = [0, -4, 0].unit();
@@ -189,6 +281,20 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn signum_and_absolute_value() {
+        let vector = [-7, 0, 3];
+        assert_eq!(vector.signum(), [-1, 0, 1]);
+        assert_eq!(vector.abs(), [7, 0, 3]);
+    }
+
+    #[test]
+    fn componentwise_minimum_and_maximum() {
+        let [left, right] = [[1, 5, -2], [3, -1, -2]];
+        assert_eq!(left.componentwise_min(right), [1, -1, -2]);
+        assert_eq!(left.componentwise_max(right), [3, 5, -2]);
+    }
+
     #[test]
     fn cross_product() {
         let actual = [3, -3, 1].cross([4, 9, 2]);
@@ -206,26 +312,4 @@ mod tests {
         let actual = [-1, 2].right();
         assert_eq!(actual, [2, 1]);
     }
-
-    #[test]
-    fn angle_between_vectors() {
-        let cases = [
-            ([[1, 0], [3, 0]], 0.0),
-            ([[2, 0], [4, 4]], FRAC_PI_4),
-            ([[3, 0], [0, 5]], FRAC_PI_2),
-            ([[4, 0], [-1, 1]], 3.0 * FRAC_PI_4),
-            ([[5, 0], [-2, 0]], PI),
-            ([[6, 0], [-3, -3]], -3.0 * FRAC_PI_4),
-            ([[7, 0], [0, -4]], -FRAC_PI_2),
-            ([[8, 0], [5, -5]], -FRAC_PI_4),
-        ];
-        for ([from, to], expected) in cases {
-            let actual = from.angle(to) / PI;
-            let expected = expected / PI;
-            assert!(
-                actual.approx_eq(expected),
-                "actual angle {actual:.2}π from {from:?} to {to:?} should equal {expected:.2}π"
-            );
-        }
-    }
 }